@@ -0,0 +1,25 @@
+use egui_fltk_frontend as frontend;
+use frontend::{egui, App, Frame, NativeOptions};
+
+struct Demo {
+    demo_windows: egui_demo_lib::DemoWindows,
+}
+
+impl App for Demo {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut Frame) {
+        self.demo_windows.ui(ctx);
+    }
+}
+
+fn main() {
+    let options = NativeOptions {
+        title: "App Demo Window".into(),
+        ..Default::default()
+    };
+    let app = Demo {
+        demo_windows: egui_demo_lib::DemoWindows::default(),
+    };
+    if let Err(e) = frontend::run(options, app) {
+        eprintln!("{}", e);
+    }
+}
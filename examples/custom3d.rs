@@ -3,13 +3,12 @@ use frontend::{
     egui,
     fltk::{
         app,
-        enums::Event,
         prelude::{GroupExt, WidgetBase, WidgetExt, WindowExt},
         window,
     },
     pollster,
     wgpu::{self, util::DeviceExt},
-    CallbackFn, Painter, RWHandleExt, RenderPass, Timer,
+    CallbackFn, Painter, RWHandleExt, RenderPass,
 };
 use std::{borrow::Cow, sync::Arc};
 use std::{cell::RefCell, rc::Rc, time::Instant};
@@ -55,8 +54,8 @@ fn main() {
     let surface_config = wgpu::SurfaceConfiguration {
         usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
         format: texture_format,
-        width: window.width() as u32,
-        height: window.height() as u32,
+        width: window.pixel_w() as u32,
+        height: window.pixel_h() as u32,
         present_mode: wgpu::PresentMode::Fifo,
     };
 
@@ -70,37 +69,13 @@ fn main() {
     // Create egui state
     let state = Rc::new(RefCell::new(state));
 
-    // Handle window events
-    window.handle({
-        let state = state.clone();
-        move |win, ev| match ev {
-            Event::Push
-            | Event::Released
-            | Event::KeyDown
-            | Event::KeyUp
-            | Event::MouseWheel
-            | Event::Resize
-            | Event::Move
-            | Event::Drag
-            | Event::Focus => {
-                // Using "if let ..." for safety.
-                if let Ok(mut state) = state.try_borrow_mut() {
-                    state.fuse_input(win, ev);
-                    true
-                } else {
-                    false
-                }
-            }
-            _ => false,
-        }
-    });
+    // Queue window events, none is dropped while the draw callback holds the state.
+    let event_queue = state.borrow().event_queue();
+    window.handle(move |win, event| event_queue.push(win, event));
 
     let egui_ctx = egui::Context::default();
     let start_time = Instant::now();
 
-    // Use Timer for auto repaint if the app is idle.
-    let mut timer = Timer::new(1);
-
     let mut custom3d = Custom3d::new(&device, &mut painter).unwrap();
 
     window.draw(move |window| {
@@ -128,25 +103,30 @@ fn main() {
             });
         });
 
-        let window_resized = state.window_resized();
-
-        // Make sure to put timer.elapsed() on the last order.
-        if app_output.repaint_after.is_zero()
-            || window_resized
-            || state.mouse_btn_pressed()
-            || timer.elapsed()
-        {
-            state.fuse_output(window, app_output.platform_output);
-            let clipped_primitive = egui_ctx.tessellate(app_output.shapes);
-            let texture = app_output.textures_delta;
-            painter.paint_jobs(&device, &queue, &state.screen_descriptor, clipped_primitive, texture);
-            app::awake();
+        // Skip painting while the window is minimized or hidden, textures are still updated.
+        if !state.window_visible() {
+            painter.update_textures(&device, &queue, app_output.textures_delta);
+            return;
         }
+
+        // Consume the resize flag, the repaint is already in progress.
+        state.window_resized();
+        state.fuse_output(window, app_output.platform_output);
+        let clipped_primitive = egui_ctx.tessellate(app_output.shapes);
+        let texture = app_output.textures_delta;
+        painter.paint_jobs(
+            &device,
+            &queue,
+            &state.screen_descriptor,
+            clipped_primitive,
+            texture,
+        );
+
+        // Wake up again only when egui asks for it, input triggers a redraw by itself.
+        state.schedule_repaint(window, app_output.repaint_after);
     });
 
-    while fltk_app.wait() {
-        window.flush();
-    }
+    fltk_app.run().unwrap();
 }
 
 const CUSTOM3D_WGPU_SHADER: &str = r#"
@@ -0,0 +1,55 @@
+use egui_fltk_frontend as frontend;
+use frontend::{
+    egui,
+    fltk::{
+        app,
+        prelude::{WidgetExt, WindowExt},
+    },
+    EguiWindow, RenderMode,
+};
+
+fn main() {
+    let fltk_app = app::App::default();
+
+    let mut window = EguiWindow::new(100, 100, 400, 300, "EguiWindow Demo");
+    window.make_resizable(true);
+
+    let mut counter = 0;
+    window.set_ui(move |ctx, frame| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Hello from egui!");
+            if ui.button("Click me").clicked() {
+                counter += 1;
+            }
+            ui.label(format!("Clicked {} times", counter));
+
+            ui.separator();
+            let mut mode = frame.state.render_mode();
+            ui.radio_value(&mut mode, RenderMode::Reactive, "Reactive");
+            ui.radio_value(&mut mode, RenderMode::Continuous, "Continuous");
+            ui.radio_value(&mut mode, RenderMode::FpsCapped(30.0), "30 FPS");
+            frame.state.set_render_mode(mode);
+
+            let mut scale = frame.painter.render_scale();
+            ui.horizontal(|ui| {
+                ui.label("Render scale:");
+                ui.radio_value(&mut scale, 0.5, "0.5x");
+                ui.radio_value(&mut scale, 1.0, "1x");
+                ui.radio_value(&mut scale, 2.0, "2x");
+            });
+            frame.painter.set_render_scale(scale);
+
+            let stats = frame.state.frame_stats();
+            ui.label(format!(
+                "{:.1} fps, last {:.1} ms, avg {:.1} ms, max {:.1} ms",
+                stats.fps(),
+                stats.last * 1000.0,
+                stats.average * 1000.0,
+                stats.max * 1000.0
+            ));
+        });
+    });
+    window.show();
+
+    fltk_app.run().unwrap();
+}
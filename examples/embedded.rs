@@ -0,0 +1,51 @@
+use egui_fltk_frontend as frontend;
+use frontend::{
+    egui,
+    fltk::{
+        app, button, group, input,
+        prelude::{GroupExt, InputExt, WidgetBase, WidgetExt, WindowExt},
+        window,
+    },
+    EguiWindow,
+};
+
+fn main() {
+    let fltk_app = app::App::default();
+
+    let mut main_win = window::Window::default()
+        .with_size(800, 500)
+        .with_label("Embedded egui");
+    let tile = group::Tile::default_fill();
+
+    // Plain FLTK widgets in the left pane.
+    let mut left = group::Group::new(0, 0, 300, 500, None);
+    let mut name = input::Input::new(80, 20, 200, 30, "Name:");
+    name.set_value("FLTK");
+    let mut btn = button::Button::new(80, 60, 200, 30, "Print");
+    btn.set_callback(move |_| println!("Hello {}", name.value()));
+    left.end();
+
+    // egui in the right pane, follows the pane when the tile border is dragged.
+    let mut egui_pane = EguiWindow::new(300, 0, 500, 500, "");
+    let mut text = String::from("egui");
+    egui_pane.set_ui(move |ctx, frame| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("egui pane");
+            ui.text_edit_singleline(&mut text);
+            ui.label(if frame.state.has_focus() {
+                "Pane has the keyboard"
+            } else {
+                "Click to type here"
+            });
+            ui.label(format!("Pointer: {:?}", ctx.pointer_latest_pos()));
+        });
+    });
+
+    tile.end();
+    main_win.end();
+    main_win.make_resizable(true);
+    main_win.show();
+    egui_pane.show();
+
+    fltk_app.run().unwrap();
+}
@@ -3,11 +3,10 @@ use egui_fltk_frontend::{
     egui::{self, Label},
     fltk::{
         app,
-        enums::Event,
         prelude::{GroupExt, WidgetBase, WidgetExt, WindowExt},
         window,
     },
-    pollster, wgpu, EguiImageConvertible, RWHandleExt, RenderPass, RetainedEguiImage, Timer,
+    pollster, wgpu, EguiImageConvertible, RWHandleExt, RenderPass, RetainedEguiImage,
 };
 use fltk::image::{JpegImage, SvgImage};
 use std::{cell::RefCell, rc::Rc, time::Instant};
@@ -53,8 +52,8 @@ fn main() {
     let surface_config = wgpu::SurfaceConfiguration {
         usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
         format: texture_format,
-        width: window.width() as u32,
-        height: window.height() as u32,
+        width: window.pixel_w() as u32,
+        height: window.pixel_h() as u32,
         present_mode: wgpu::PresentMode::Fifo,
     };
 
@@ -73,30 +72,9 @@ fn main() {
     // Create egui state
     let state = Rc::new(RefCell::new(state));
 
-    // Handle window events
-    window.handle({
-        let state = state.clone();
-        move |win, ev| match ev {
-            Event::Push
-            | Event::Released
-            | Event::KeyDown
-            | Event::KeyUp
-            | Event::MouseWheel
-            | Event::Resize
-            | Event::Move
-            | Event::Drag
-            | Event::Focus => {
-                // Using "if let ..." for safety.
-                if let Ok(mut state) = state.try_borrow_mut() {
-                    state.fuse_input(win, ev);
-                    true
-                } else {
-                    false
-                }
-            }
-            _ => false,
-        }
-    });
+    // Queue window events, none is dropped while the draw callback holds the state.
+    let event_queue = state.borrow().event_queue();
+    window.handle(move |win, event| event_queue.push(win, event));
 
     let egui_ctx = egui::Context::default();
     let start_time = Instant::now();
@@ -112,9 +90,6 @@ fn main() {
         .egui_image("nature.jpg")
         .unwrap();
 
-    // Use Timer for auto repaint if the app is idle.
-    let mut timer = Timer::new(1);
-
     let mut quit = false;
 
     window.draw(move |window| {
@@ -141,38 +116,32 @@ fn main() {
             });
         });
 
-        let window_resized = state.window_resized();
-
-        // Make sure to put timer.elapsed() on the last order.
-        if app_output.repaint_after.is_zero()
-            || window_resized
-            || state.mouse_btn_pressed()
-            || timer.elapsed()
-        {
-            state.fuse_output(window, app_output.platform_output);
-            let clipped_primitive = egui_ctx.tessellate(app_output.shapes);
-            let texture = app_output.textures_delta;
-            painter.paint_jobs(
-                &device,
-                &queue,
-                &state.screen_descriptor,
-                clipped_primitive,
-                texture,
-            );
-            app::awake();
+        // Skip painting while the window is minimized or hidden, textures are still updated.
+        if !state.window_visible() {
+            painter.update_textures(&device, &queue, app_output.textures_delta);
+            return;
         }
 
+        // Consume the resize flag, the repaint is already in progress.
+        state.window_resized();
+        state.fuse_output(window, app_output.platform_output);
+        let clipped_primitive = egui_ctx.tessellate(app_output.shapes);
+        let texture = app_output.textures_delta;
+        painter.paint_jobs(
+            &device,
+            &queue,
+            &state.screen_descriptor,
+            clipped_primitive,
+            texture,
+        );
+
+        // Wake up again only when egui asks for it, input triggers a redraw by itself.
+        state.schedule_repaint(window, app_output.repaint_after);
+
         if quit {
             app::quit();
         }
     });
 
-    let mut init = true;
-    while fltk_app.wait() {
-        if init {
-            fltk_app.redraw();
-            init = false;
-        }
-        window.flush();
-    }
+    fltk_app.run().unwrap();
 }
@@ -3,13 +3,12 @@ use frontend::{
     egui,
     fltk::{
         app,
-        enums::Event,
         prelude::{GroupExt, WidgetBase, WidgetExt, WindowExt},
         window,
     },
-    pollster, wgpu, RWHandleExt, RenderPass, Timer,
+    pollster, wgpu, RWHandleExt, RenderPass,
 };
-use std::{cell::RefCell, rc::Rc, time::Instant};
+use std::{cell::RefCell, rc::Rc};
 
 fn main() {
     let fltk_app = app::App::default();
@@ -60,8 +59,8 @@ fn main() {
     let surface_config = wgpu::SurfaceConfiguration {
         usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
         format: texture_format,
-        width: window.width() as u32,
-        height: window.height() as u32,
+        width: window.pixel_w() as u32,
+        height: window.pixel_h() as u32,
         present_mode: wgpu::PresentMode::Fifo,
     };
 
@@ -71,72 +70,54 @@ fn main() {
     let render_pass = RenderPass::new(&device, texture_format, 1);
     let (mut painter, state) =
         frontend::begin_with(&mut window, render_pass, surface, surface_config);
+    // Keep the instance around to rebuild the surface if the native window handle changes.
+    painter.set_instance(instance);
     let state = Rc::new(RefCell::new(state));
 
+    // Queue window events, none is dropped while the draw callback holds the state.
+    let event_queue = state.borrow().event_queue();
     window.handle({
-        let state = state.clone();
-        move |win, event| match event {
-            Event::Push
-            | Event::Released
-            | Event::KeyDown
-            | Event::KeyUp
-            | Event::MouseWheel
-            | Event::Resize
-            | Event::Move
-            | Event::Drag
-            | Event::Focus => {
-                // Using "if let ..." for safety.
-                if let Ok(mut state) = state.try_borrow_mut() {
-                    state.fuse_input(win, event);
-                    true
-                } else {
-                    false
-                }
-            }
-            _ => false,
-        }
+        let event_queue = event_queue.clone();
+        move |win, event| event_queue.push(win, event)
     });
 
     // Display the demo application that ships with egui.
     let mut demo_app = egui_demo_lib::DemoWindows::default();
     let egui_ctx = egui::Context::default();
-    let start_time = Instant::now();
-
-    // Use Timer for auto repaint if the app is idle.
-    let mut timer = Timer::new(1);
+    // Repaint requests from other threads wake up the event loop.
+    let _repaint = frontend::repaint_on_request(&egui_ctx, &window, &event_queue);
 
     window.draw(move |window| {
         let mut state = state.borrow_mut();
-        state.start_time(start_time.elapsed().as_secs_f64());
+        painter.sync_surface(&device, window);
 
         // Draw the demo application.
         let app_output = egui_ctx.run(state.take_input(), |ctx| {
             demo_app.ui(ctx);
         });
 
-        let window_resized = state.window_resized();
-
-        // Make sure to put timer.elapsed() on the last order.
-        if app_output.repaint_after.is_zero()
-            || window_resized
-            || state.mouse_btn_pressed()
-            || timer.elapsed()
-        {
-            state.fuse_output(window, app_output.platform_output);
-            let clipped_primitive = egui_ctx.tessellate(app_output.shapes);
-            let texture = app_output.textures_delta;
-            painter.paint_jobs(
-                &device,
-                &queue,
-                &state.screen_descriptor,
-                clipped_primitive,
-                texture,
-            );
-            app::awake();
+        // Skip painting while the window is minimized or hidden, textures are still updated.
+        if !state.window_visible() {
+            painter.update_textures(&device, &queue, app_output.textures_delta);
+            return;
         }
+
+        // Consume the resize flag, the repaint is already in progress.
+        state.window_resized();
+        state.fuse_output(window, app_output.platform_output);
+        let clipped_primitive = egui_ctx.tessellate(app_output.shapes);
+        let texture = app_output.textures_delta;
+        painter.paint_jobs(
+            &device,
+            &queue,
+            &state.screen_descriptor,
+            clipped_primitive,
+            texture,
+        );
+
+        // Wake up again only when egui asks for it, input triggers a redraw by itself.
+        state.schedule_repaint(window, app_output.repaint_after);
     });
 
-    while fltk_app.wait() {
-        window.flush();
-    }
+    fltk_app.run().unwrap();
 }
@@ -5,11 +5,10 @@ use egui_fltk_frontend::{
     egui,
     fltk::{
         app,
-        enums::Event,
         prelude::{GroupExt, WidgetBase, WidgetExt, WindowExt},
         window,
     },
-    pollster, wgpu, RWHandleExt, RenderPass, Timer,
+    pollster, wgpu, RWHandleExt, RenderPass,
 };
 
 use std::borrow::Cow;
@@ -57,8 +56,8 @@ fn main() {
     let surface_config = wgpu::SurfaceConfiguration {
         usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
         format: texture_format,
-        width: window.width() as u32,
-        height: window.height() as u32,
+        width: window.pixel_w() as u32,
+        height: window.pixel_h() as u32,
         present_mode: wgpu::PresentMode::Fifo,
     };
 
@@ -75,30 +74,9 @@ fn main() {
     // Create egui state
     let state = Rc::new(RefCell::new(state));
 
-    // Handle window events
-    window.handle({
-        let state = state.clone();
-        move |win, ev| match ev {
-            Event::Push
-            | Event::Released
-            | Event::KeyDown
-            | Event::KeyUp
-            | Event::MouseWheel
-            | Event::Resize
-            | Event::Move
-            | Event::Drag
-            | Event::Focus => {
-                // Using "if let ..." for safety.
-                if let Ok(mut state) = state.try_borrow_mut() {
-                    state.fuse_input(win, ev);
-                    true
-                } else {
-                    false
-                }
-            }
-            _ => false,
-        }
-    });
+    // Queue window events, none is dropped while the draw callback holds the state.
+    let event_queue = state.borrow().event_queue();
+    window.handle(move |win, event| event_queue.push(win, event));
 
     // Prepare scene
     let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
@@ -137,8 +115,8 @@ fn main() {
     let mut smaa_target = smaa::SmaaTarget::new(
         &device,
         &queue,
-        window.width() as u32,
-        window.height() as u32,
+        window.pixel_w() as u32,
+        window.pixel_h() as u32,
         texture_format,
         smaa::SmaaMode::Disabled,
     );
@@ -146,9 +124,6 @@ fn main() {
     let egui_ctx = egui::Context::default();
     let start_time = Instant::now();
 
-    // Use Timer for auto repaint if the app is idle.
-    let mut timer = Timer::new(1);
-
     let mut quit = false;
 
     let mut disable_smaa = true;
@@ -175,8 +150,8 @@ fn main() {
                                 smaa_target = smaa::SmaaTarget::new(
                                     &device,
                                     &queue,
-                                    window.width() as u32,
-                                    window.height() as u32,
+                                    window.pixel_w() as u32,
+                                    window.pixel_h() as u32,
                                     texture_format,
                                     smaa::SmaaMode::Disabled,
                                 );
@@ -185,8 +160,8 @@ fn main() {
                                 smaa_target = smaa::SmaaTarget::new(
                                     &device,
                                     &queue,
-                                    window.width() as u32,
-                                    window.height() as u32,
+                                    window.pixel_w() as u32,
+                                    window.pixel_h() as u32,
                                     texture_format,
                                     smaa::SmaaMode::Smaa1X,
                                 );
@@ -206,91 +181,84 @@ fn main() {
 
         let window_resized = state.window_resized();
 
-        // Make sure to put timer.elapsed() on the last order.
-        if app_output.repaint_after.is_zero()
-            || window_resized
-            || state.mouse_btn_pressed()
-            || timer.elapsed()
-        {
-            state.fuse_output(window, app_output.platform_output);
-            let clipped_primitive = egui_ctx.tessellate(app_output.shapes);
-            let texture = app_output.textures_delta;
-
-            // Get calculated ScreenDescriptor
-            let screen_descriptor = &state.screen_descriptor;
-
-            // Resize surface according to window screen_descriptor
-            if window_resized {
-                let size = screen_descriptor.size_in_pixels;
-                painter.surface_config.width = size[0];
-                painter.surface_config.height = size[1];
-                painter.surface.configure(&device, &painter.surface_config);
-                smaa_target.resize(&device, size[0], size[1]);
-            }
+        // Skip painting while the window is minimized or hidden, textures are still updated.
+        if !state.window_visible() {
+            painter.update_textures(&device, &queue, app_output.textures_delta);
+            return;
+        }
 
-            // Record all render passes.
-            match painter.surface.get_current_texture() {
-                Ok(frame) => {
-                    let mut encoder =
-                        device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                            label: Some("encoder"),
-                        });
-
-                    let view = frame
-                        .texture
-                        .create_view(&wgpu::TextureViewDescriptor::default());
-                    let smaa_view = smaa_target.start_frame(&device, &queue, &view);
-                    {
-                        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                            label: None,
-                            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                                view: &smaa_view,
-                                resolve_target: None,
-                                ops: wgpu::Operations {
-                                    load: wgpu::LoadOp::Clear(wgpu::Color::GREEN),
-                                    store: true,
-                                },
-                            })],
-                            depth_stencil_attachment: None,
-                        });
-
-                        // Draw Triangle Texture
-                        rpass.set_pipeline(&render_pipeline);
-                        rpass.draw(0..3, 0..1);
-
-                        // Draw Egui Texture
-                        painter.paint_with_rpass(
-                            &mut rpass,
-                            &device,
-                            &queue,
-                            screen_descriptor,
-                            clipped_primitive,
-                            texture,
-                        );
-                    }
-
-                    // Submit command buffer
-                    let cm_buffer = encoder.finish();
-                    queue.submit(Some(cm_buffer));
-                    smaa_view.resolve();
-                    frame.present();
-                }
-                Err(e) => return eprintln!("Dropped frame with error: {}", e),
-            };
-            app::awake();
+        state.fuse_output(window, app_output.platform_output);
+        let clipped_primitive = egui_ctx.tessellate(app_output.shapes);
+        let texture = app_output.textures_delta;
+
+        // Get calculated ScreenDescriptor
+        let screen_descriptor = &state.screen_descriptor;
+
+        // Resize surface according to window screen_descriptor
+        if window_resized {
+            let size = screen_descriptor.size_in_pixels;
+            painter.surface_config.width = size[0];
+            painter.surface_config.height = size[1];
+            painter.surface.configure(&device, &painter.surface_config);
+            smaa_target.resize(&device, size[0], size[1]);
         }
 
+        // Record all render passes.
+        match painter.surface.get_current_texture() {
+            Ok(frame) => {
+                let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("encoder"),
+                });
+
+                let view = frame
+                    .texture
+                    .create_view(&wgpu::TextureViewDescriptor::default());
+                let smaa_view = smaa_target.start_frame(&device, &queue, &view);
+                {
+                    let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                        label: None,
+                        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                            view: &smaa_view,
+                            resolve_target: None,
+                            ops: wgpu::Operations {
+                                load: wgpu::LoadOp::Clear(wgpu::Color::GREEN),
+                                store: true,
+                            },
+                        })],
+                        depth_stencil_attachment: None,
+                    });
+
+                    // Draw Triangle Texture
+                    rpass.set_pipeline(&render_pipeline);
+                    rpass.draw(0..3, 0..1);
+
+                    // Draw Egui Texture
+                    painter.paint_with_rpass(
+                        &mut rpass,
+                        &device,
+                        &queue,
+                        screen_descriptor,
+                        clipped_primitive,
+                        texture,
+                    );
+                }
+
+                // Submit command buffer
+                let cm_buffer = encoder.finish();
+                queue.submit(Some(cm_buffer));
+                smaa_view.resolve();
+                frame.present();
+            }
+            Err(e) => eprintln!("Dropped frame with error: {}", e),
+        };
+
+        // Wake up again only when egui asks for it, input triggers a redraw by itself.
+        state.schedule_repaint(window, app_output.repaint_after);
+
         if quit {
             app::quit();
         }
     });
 
-    let mut init = true;
-    while fltk_app.wait() {
-        if init {
-            fltk_app.redraw();
-            init = false;
-        }
-        window.flush();
-    }
+    fltk_app.run().unwrap();
 }
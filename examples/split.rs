@@ -0,0 +1,137 @@
+use egui_fltk_frontend as frontend;
+use frontend::{
+    egui,
+    fltk::{
+        app,
+        prelude::{GroupExt, WidgetBase, WidgetExt, WindowExt},
+        window,
+    },
+    pollster, wgpu, EguiState, RWHandleExt, RenderPass,
+};
+use std::{cell::RefCell, rc::Rc};
+
+fn main() {
+    let fltk_app = app::App::default();
+
+    let mut window = window::GlWindow::default()
+        .with_size(1000, 600)
+        .center_screen();
+    window.set_label("Split Screen");
+    window.make_resizable(true);
+    window.end();
+    window.show();
+    window.make_current();
+
+    let instance = wgpu::Instance::new(wgpu::Backends::PRIMARY);
+    let surface = unsafe { instance.create_surface(&window.use_compat()) };
+
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::LowPower,
+        compatible_surface: Some(&surface),
+        force_fallback_adapter: false,
+    }))
+    .unwrap();
+
+    let (device, queue) = pollster::block_on(adapter.request_device(
+        &wgpu::DeviceDescriptor {
+            features: wgpu::Features::default(),
+            limits: wgpu::Limits::downlevel_webgl2_defaults(),
+            label: None,
+        },
+        None,
+    ))
+    .unwrap();
+
+    let texture_format = surface.get_supported_formats(&adapter)[0];
+    let surface_config = wgpu::SurfaceConfiguration {
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        format: texture_format,
+        width: window.pixel_w() as u32,
+        height: window.pixel_h() as u32,
+        present_mode: wgpu::PresentMode::Fifo,
+    };
+
+    surface.configure(&device, &surface_config);
+
+    // One painter and surface, one state and render pass per context.
+    let render_pass = RenderPass::new(&device, texture_format, 1);
+    let (mut painter, left) =
+        frontend::begin_with(&mut window, render_pass, surface, surface_config);
+    painter.set_instance(instance);
+    let right = EguiState::new(&mut window);
+    let right_pass = painter.add_region(&device);
+    let left = Rc::new(RefCell::new(left));
+    let right = Rc::new(RefCell::new(right));
+
+    // Both states see every event, each keeps the ones inside its region.
+    let left_queue = left.borrow().event_queue();
+    let right_queue = right.borrow().event_queue();
+    window.handle({
+        let left_queue = left_queue.clone();
+        let right_queue = right_queue.clone();
+        move |win, event| {
+            let left = left_queue.push(win, event);
+            let right = right_queue.push(win, event);
+            left || right
+        }
+    });
+
+    let mut demo_app = egui_demo_lib::DemoWindows::default();
+    let left_ctx = egui::Context::default();
+    let right_ctx = egui::Context::default();
+    let _left_repaint = frontend::repaint_on_request(&left_ctx, &window, &left_queue);
+    let _right_repaint = frontend::repaint_on_request(&right_ctx, &window, &right_queue);
+    let mut counter = 0;
+
+    window.draw(move |window| {
+        let mut left = left.borrow_mut();
+        let mut right = right.borrow_mut();
+        let (w, h) = (window.w(), window.h());
+        left.set_region(Some((0, 0, w / 2, h)));
+        right.set_region(Some((w / 2, 0, w - w / 2, h)));
+        painter.sync_surface(&device, window);
+
+        let left_output = left_ctx.run(left.take_input(), |ctx| {
+            demo_app.ui(ctx);
+        });
+        let right_output = right_ctx.run(right.take_input(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.heading("Second context");
+                if ui.button("Click me").clicked() {
+                    counter += 1;
+                }
+                ui.label(format!("Clicked {} times", counter));
+            });
+        });
+
+        left.window_resized();
+        right.window_resized();
+        left.fuse_output(window, left_output.platform_output);
+        right.fuse_output(window, right_output.platform_output);
+        let regions = vec![
+            left.region_paint(
+                0,
+                left_ctx.tessellate(left_output.shapes),
+                left_output.textures_delta,
+            ),
+            right.region_paint(
+                right_pass,
+                right_ctx.tessellate(right_output.shapes),
+                right_output.textures_delta,
+            ),
+        ];
+        // A zero size skips painting while the window is minimized or hidden, textures are
+        // still updated.
+        let size_in_pixels = if left.window_visible() {
+            [window.pixel_w() as u32, window.pixel_h() as u32]
+        } else {
+            [0, 0]
+        };
+        painter.paint_regions(&device, &queue, size_in_pixels, regions);
+
+        left.schedule_repaint(window, left_output.repaint_after);
+        right.schedule_repaint(window, right_output.repaint_after);
+    });
+
+    fltk_app.run().unwrap();
+}
@@ -0,0 +1,129 @@
+use egui_fltk_frontend as frontend;
+use frontend::{
+    egui,
+    fltk::{
+        app,
+        prelude::{GroupExt, WidgetBase, WidgetExt, WindowExt},
+        window,
+    },
+    pollster, wgpu, RWHandleExt, RenderPass, ThreadedUi,
+};
+use std::{cell::RefCell, rc::Rc};
+
+fn main() {
+    let fltk_app = app::App::default();
+
+    // Initialize fltk windows with minimal size:
+    let mut window = window::GlWindow::default()
+        .with_size(800, 600)
+        .center_screen();
+    window.set_label("Threaded Demo Window");
+    window.make_resizable(true);
+    window.end();
+    window.show();
+    window.make_current();
+
+    // wgpu::Backends::PRIMARY can be changed accordingly, .e.g: (wgpu::Backends::VULKAN, wgpu::Backends::GL .etc)
+    #[cfg(target_os = "windows")]
+    let instance = wgpu::Instance::new(wgpu::Backends::DX12);
+
+    #[cfg(target_os = "linux")]
+    let instance = wgpu::Instance::new(wgpu::Backends::PRIMARY);
+
+    #[cfg(target_os = "macos")]
+    let instance = wgpu::Instance::new(wgpu::Backends::METAL);
+
+    // let surface = unsafe { instance.create_surface(&window) };
+    // window.use_compat() for raw-window-handle 4.x compatible
+    let surface = unsafe { instance.create_surface(&window.use_compat()) };
+
+    // WGPU 0.11+ support force fallback (if HW implementation not supported), set it to true or false (optional).
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::LowPower,
+        compatible_surface: Some(&surface),
+        force_fallback_adapter: false,
+    }))
+    .unwrap();
+
+    let (device, queue) = pollster::block_on(adapter.request_device(
+        &wgpu::DeviceDescriptor {
+            features: wgpu::Features::default(),
+            limits: wgpu::Limits::downlevel_webgl2_defaults(),
+            label: None,
+        },
+        None,
+    ))
+    .unwrap();
+
+    let texture_format = surface.get_supported_formats(&adapter)[0];
+    let surface_config = wgpu::SurfaceConfiguration {
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        format: texture_format,
+        width: window.pixel_w() as u32,
+        height: window.pixel_h() as u32,
+        present_mode: wgpu::PresentMode::Fifo,
+    };
+
+    surface.configure(&device, &surface_config);
+
+    // Prepare back and front.
+    let render_pass = RenderPass::new(&device, texture_format, 1);
+    let (mut painter, state) =
+        frontend::begin_with(&mut window, render_pass, surface, surface_config);
+    // Keep the instance around to rebuild the surface if the native window handle changes.
+    painter.set_instance(instance);
+    let state = Rc::new(RefCell::new(state));
+
+    // Queue window events, none is dropped while the draw callback holds the state.
+    let event_queue = state.borrow().event_queue();
+    window.handle(move |win, event| event_queue.push(win, event));
+
+    // Heavy UI running on a worker thread, the window stays responsive meanwhile.
+    let egui_ctx = egui::Context::default();
+    let mut ui = ThreadedUi::spawn(egui_ctx, &window, |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            egui::ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    egui::Grid::new("big_table").striped(true).show(ui, |ui| {
+                        for row in 0..5_000 {
+                            for col in 0..6 {
+                                ui.label(format!("{}:{}", row, col));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+        });
+    });
+
+    window.draw(move |window| {
+        let mut state = state.borrow_mut();
+        painter.sync_surface(&device, window);
+        // Apply the queued events first, a Show makes the window visible again.
+        state.fuse_queue();
+
+        // Skip painting while the window is minimized or hidden.
+        if !state.window_visible() {
+            return;
+        }
+
+        // Take the latest frame delivered by the worker first, so new input can be submitted.
+        let frame = ui.try_recv();
+        ui.submit(&mut state);
+        if let Some(frame) = frame {
+            state.window_resized();
+            state.fuse_output(window, frame.platform_output);
+            painter.paint_jobs(
+                &device,
+                &queue,
+                &state.screen_descriptor,
+                frame.clipped_primitives,
+                frame.textures_delta,
+            );
+            state.schedule_repaint(window, frame.repaint_after);
+        }
+    });
+
+    fltk_app.run().unwrap();
+}
@@ -440,6 +440,25 @@ impl<'a> RenderPass<'a> {
         rpass: &mut wgpu::RenderPass<'rpass>,
         paint_jobs: Vec<egui::epaint::ClippedPrimitive>,
         screen_descriptor: &ScreenDescriptor,
+    ) {
+        self.execute_with_renderpass_at(
+            rpass,
+            paint_jobs,
+            screen_descriptor,
+            [0, 0],
+            screen_descriptor.size_in_pixels,
+        );
+    }
+
+    /// Executes the egui render pass onto an existing wgpu renderpass, into the region at `origin`
+    /// (physical pixels) of a `target_size` attachment, so several UIs can share one target.
+    pub fn execute_with_renderpass_at<'rpass>(
+        &'rpass self,
+        rpass: &mut wgpu::RenderPass<'rpass>,
+        paint_jobs: Vec<egui::epaint::ClippedPrimitive>,
+        screen_descriptor: &ScreenDescriptor,
+        origin: [u32; 2],
+        target_size: [u32; 2],
     ) {
         let pixels_per_point = screen_descriptor.pixels_per_point;
         let size_in_pixels = screen_descriptor.size_in_pixels;
@@ -458,8 +477,8 @@ impl<'a> RenderPass<'a> {
         {
             if needs_reset {
                 rpass.set_viewport(
-                    0.0,
-                    0.0,
+                    origin[0] as f32,
+                    origin[1] as f32,
                     size_in_pixels[0] as f32,
                     size_in_pixels[1] as f32,
                     0.0,
@@ -471,7 +490,8 @@ impl<'a> RenderPass<'a> {
             }
 
             {
-                let rect = ScissorRect::new(&clip_rect, pixels_per_point, size_in_pixels);
+                let rect = ScissorRect::new(&clip_rect, pixels_per_point, size_in_pixels)
+                    .offset(origin, target_size);
 
                 if rect.width == 0 || rect.height == 0 {
                     // Skip rendering with zero-sized clip areas.
@@ -528,8 +548,8 @@ impl<'a> RenderPass<'a> {
                             let rect_max_y = rect_max_y.round();
 
                             rpass.set_viewport(
-                                rect_min_x,
-                                rect_min_y,
+                                origin[0] as f32 + rect_min_x,
+                                origin[1] as f32 + rect_min_y,
                                 rect_max_x - rect_min_x,
                                 rect_max_y - rect_min_y,
                                 0.0,
@@ -552,7 +572,7 @@ impl<'a> RenderPass<'a> {
             }
         }
 
-        rpass.set_scissor_rect(0, 0, size_in_pixels[0], size_in_pixels[1]);
+        rpass.set_scissor_rect(0, 0, target_size[0], target_size[1]);
     }
 
     /// Should be called before `execute()`.
@@ -896,4 +916,16 @@ impl ScissorRect {
             height: clip_max_y - clip_min_y,
         }
     }
+
+    /// Move into the region at `origin`, clamped to the attachment.
+    fn offset(self, origin: [u32; 2], target_size: [u32; 2]) -> Self {
+        let x = (self.x + origin[0]).min(target_size[0]);
+        let y = (self.y + origin[1]).min(target_size[1]);
+        ScissorRect {
+            x,
+            y,
+            width: self.width.min(target_size[0] - x),
+            height: self.height.min(target_size[1] - y),
+        }
+    }
 }
@@ -0,0 +1,71 @@
+use fltk::app;
+use std::sync::mpsc;
+
+/// Create a typed channel delivering messages from any thread into egui frames.
+///
+/// Each channel owns its queue, unlike FLTK's global `app::channel`, so messages of other types
+/// aren't dropped. Sending requests a repaint of `ctx`, pair it with `repaint_on_request` so the
+/// window is redrawn.
+pub fn message_channel<T>(ctx: &egui::Context) -> (MessageSender<T>, MessageReceiver<T>)
+where
+    T: Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel::<T>();
+    (
+        MessageSender {
+            sender,
+            ctx: ctx.clone(),
+        },
+        MessageReceiver { receiver },
+    )
+}
+
+/// Sending half, can be cloned and moved across threads.
+pub struct MessageSender<T> {
+    sender: mpsc::Sender<T>,
+    ctx: egui::Context,
+}
+
+impl<T> Clone for MessageSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            ctx: self.ctx.clone(),
+        }
+    }
+}
+
+impl<T> MessageSender<T>
+where
+    T: Send + 'static,
+{
+    /// Send a message and wake up the event loop.
+    ///
+    /// The message is dropped if the receiver is gone.
+    pub fn send(&self, msg: T) {
+        if self.sender.send(msg).is_ok() {
+            self.ctx.request_repaint();
+            app::awake();
+        }
+    }
+}
+
+/// Receiving half, drain it inside the egui frame closure.
+pub struct MessageReceiver<T> {
+    receiver: mpsc::Receiver<T>,
+}
+
+impl<T> MessageReceiver<T>
+where
+    T: Send + 'static,
+{
+    /// Receive a single pending message, if any.
+    pub fn recv(&self) -> Option<T> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Iterate over all pending messages without blocking.
+    pub fn try_iter(&self) -> impl Iterator<Item = T> + '_ {
+        self.receiver.try_iter()
+    }
+}
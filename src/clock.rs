@@ -0,0 +1,128 @@
+use std::{cell::Cell, rc::Rc, time::Duration, time::Instant};
+
+/// Time source of `EguiState`, drives `RawInput::time`, `predicted_dt` and repaint scheduling.
+pub trait Clock {
+    /// Current time in seconds, called once per frame by `EguiState::take_input`.
+    fn now(&mut self) -> f64;
+
+    /// Map egui's `repaint_after` to a real (wall clock) delay, `Duration::MAX` means never.
+    fn repaint_delay(&self, repaint_after: Duration) -> Duration {
+        repaint_after
+    }
+}
+
+/// Wall clock time since creation (default).
+pub struct RealClock {
+    start: Instant,
+}
+
+impl Default for RealClock {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Clock for RealClock {
+    fn now(&mut self) -> f64 {
+        self.start.elapsed().as_secs_f64()
+    }
+}
+
+/// Advances by a fixed step each frame, regardless of real time.
+///
+/// Pending animations are repainted right away, so every frame moves time by exactly one step.
+pub struct FixedStepClock {
+    time: f64,
+    step: f64,
+}
+
+impl FixedStepClock {
+    /// Step in seconds, e.g: 1.0 / 60.0
+    pub fn new(step: f64) -> Self {
+        Self { time: -step, step }
+    }
+}
+
+impl Clock for FixedStepClock {
+    fn now(&mut self) -> f64 {
+        self.time += self.step;
+        self.time
+    }
+
+    fn repaint_delay(&self, repaint_after: Duration) -> Duration {
+        if repaint_after == Duration::MAX {
+            repaint_after
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+/// Time set explicitly by the app, cloned handles share the same time.
+///
+/// Repaints aren't scheduled, the app redraws after moving the time.
+#[derive(Clone, Default)]
+pub struct ManualClock {
+    time: Rc<Cell<f64>>,
+}
+
+impl ManualClock {
+    pub fn new(time: f64) -> Self {
+        Self {
+            time: Rc::new(Cell::new(time)),
+        }
+    }
+
+    pub fn set(&self, time: f64) {
+        self.time.set(time);
+    }
+
+    pub fn advance(&self, dt: f64) {
+        self.time.set(self.time.get() + dt);
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time.get()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&mut self) -> f64 {
+        self.time.get()
+    }
+
+    fn repaint_delay(&self, _repaint_after: Duration) -> Duration {
+        Duration::MAX
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_step_clock() {
+        let mut clock = FixedStepClock::new(0.5);
+        assert_eq!(clock.now(), 0.0);
+        assert_eq!(clock.now(), 0.5);
+        assert_eq!(clock.now(), 1.0);
+        assert_eq!(clock.repaint_delay(Duration::from_secs(1)), Duration::ZERO);
+        assert_eq!(clock.repaint_delay(Duration::MAX), Duration::MAX);
+    }
+
+    #[test]
+    fn manual_clock_shares_time() {
+        let handle = ManualClock::new(1.0);
+        let mut clock = handle.clone();
+        assert_eq!(clock.now(), 1.0);
+        handle.advance(0.25);
+        assert_eq!(clock.now(), 1.25);
+        handle.set(10.0);
+        assert_eq!(clock.now(), 10.0);
+        assert_eq!(handle.time(), 10.0);
+        assert_eq!(clock.now(), 10.0);
+        assert_eq!(clock.repaint_delay(Duration::ZERO), Duration::MAX);
+    }
+}
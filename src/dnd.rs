@@ -0,0 +1,59 @@
+use fltk::app;
+use std::path::PathBuf;
+
+/// Data dragged out of egui into other applications.
+///
+/// FLTK only offers plain text to drop targets, `Files` included.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DragPayload {
+    /// Plain text.
+    Text(String),
+    /// File paths, sent as the text of a `file://` uri list (plain paths on windows). Targets
+    /// accepting text get the list, file managers expecting real file drops (text/uri-list,
+    /// CF_HDROP .etc) don't receive files.
+    Files(Vec<PathBuf>),
+}
+
+impl DragPayload {
+    fn to_text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::Files(paths) => {
+                let mut out = String::new();
+                for path in paths {
+                    #[cfg(target_os = "windows")]
+                    out.push_str(&path.to_string_lossy());
+
+                    #[cfg(not(target_os = "windows"))]
+                    {
+                        out.push_str("file://");
+                        encode_uri_path(&path.to_string_lossy(), &mut out);
+                    }
+
+                    out.push_str("\r\n");
+                }
+                out
+            }
+        }
+    }
+
+    /// Hand the payload over to FLTK and start a native drag, blocks until it's dropped.
+    pub(crate) fn start_dnd(&self) {
+        // FLTK drags whatever is in the selection buffer, not the clipboard (`app::copy`), which
+        // is left alone. On X11 the selection buffer is also the primary (middle-click) selection.
+        app::copy2(&self.to_text());
+        app::dnd();
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn encode_uri_path(path: &str, out: &mut String) {
+    for b in path.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(b as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+}
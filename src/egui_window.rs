@@ -0,0 +1,461 @@
+#[cfg(feature = "persistence")]
+use crate::Storage;
+use crate::{
+    begin_with, repaint_on_request, EguiState, EventQueue, Painter, RWHandleExt, RenderPass,
+    RepaintOnRequest, WindowGeometry,
+};
+use fltk::{
+    app, enums,
+    prelude::{FltkError, GroupExt, WidgetBase, WidgetExt, WindowExt},
+    window,
+};
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+};
+
+/// The window type hosting egui.
+#[cfg(feature = "enable-glwindow")]
+pub type AppWindow = window::GlWindow;
+
+/// The window type hosting egui.
+#[cfg(not(feature = "enable-glwindow"))]
+pub type AppWindow = window::Window;
+
+/// WGPU settings used to create the device and surface of an `EguiWindow`.
+#[derive(Clone, Debug)]
+pub struct WgpuOptions {
+    pub backends: wgpu::Backends,
+    pub power_preference: wgpu::PowerPreference,
+    pub present_mode: wgpu::PresentMode,
+    pub features: wgpu::Features,
+    pub limits: wgpu::Limits,
+}
+
+impl Default for WgpuOptions {
+    fn default() -> Self {
+        Self {
+            backends: wgpu::Backends::PRIMARY,
+            power_preference: wgpu::PowerPreference::LowPower,
+            present_mode: wgpu::PresentMode::Fifo,
+            features: wgpu::Features::default(),
+            limits: wgpu::Limits::downlevel_webgl2_defaults(),
+        }
+    }
+}
+
+/// Errors that can occur while setting up the frontend.
+#[derive(Debug)]
+pub enum RunError {
+    Fltk(FltkError),
+    /// No adapter compatible with the window surface.
+    NoAdapter,
+    RequestDevice(wgpu::RequestDeviceError),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fltk(e) => write!(f, "FLTK error: {}", e),
+            Self::NoAdapter => write!(f, "no compatible wgpu adapter found"),
+            Self::RequestDevice(e) => write!(f, "failed to request wgpu device: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+impl From<FltkError> for RunError {
+    fn from(e: FltkError) -> Self {
+        Self::Fltk(e)
+    }
+}
+
+/// Access to the frontend's resources from within the UI closure.
+pub struct Frame<'a> {
+    pub device: &'a wgpu::Device,
+    pub queue: &'a wgpu::Queue,
+    pub painter: &'a mut Painter<'static>,
+    pub state: &'a mut EguiState,
+    pub window: &'a mut AppWindow,
+    #[cfg(feature = "persistence")]
+    storage: Option<&'a mut Storage>,
+    close: bool,
+}
+
+impl<'a> Frame<'a> {
+    /// The storage set with `EguiWindow::set_storage`, e.g: to restore app state in `App::setup`.
+    #[cfg(feature = "persistence")]
+    pub fn storage(&mut self) -> Option<&mut Storage> {
+        self.storage.as_deref_mut()
+    }
+
+    /// Hide the window after this frame.
+    pub fn close(&mut self) {
+        self.close = true;
+    }
+}
+
+#[cfg(feature = "persistence")]
+const GEOMETRY_KEY: &str = "window_geometry";
+
+#[cfg(feature = "persistence")]
+const VISUAL_SCALE_KEY: &str = "visual_scale";
+
+type UiFn = Box<dyn FnMut(&egui::Context, &mut Frame)>;
+
+#[cfg(feature = "persistence")]
+type SaveFn = Box<dyn FnMut(&mut Storage)>;
+
+struct Gpu {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    painter: Painter<'static>,
+    state: EguiState,
+}
+
+impl Gpu {
+    fn new(window: &mut AppWindow, options: &WgpuOptions) -> Result<Self, RunError> {
+        #[cfg(feature = "enable-glwindow")]
+        window.make_current();
+
+        let instance = wgpu::Instance::new(options.backends);
+        let surface = unsafe { instance.create_surface(&window.use_compat()) };
+
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: options.power_preference,
+            compatible_surface: Some(&surface),
+            force_fallback_adapter: false,
+        }))
+        .ok_or(RunError::NoAdapter)?;
+
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                features: options.features,
+                limits: options.limits.clone(),
+                label: None,
+            },
+            None,
+        ))
+        .map_err(RunError::RequestDevice)?;
+
+        let texture_format = surface.get_supported_formats(&adapter)[0];
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: texture_format,
+            width: window.pixel_w() as u32,
+            height: window.pixel_h() as u32,
+            present_mode: options.present_mode,
+        };
+        surface.configure(&device, &surface_config);
+
+        let render_pass = RenderPass::new(&device, texture_format, 1);
+        let (mut painter, state) = begin_with(window, render_pass, surface, surface_config);
+        painter.set_instance(instance);
+        Ok(Self {
+            device,
+            queue,
+            painter,
+            state,
+        })
+    }
+}
+
+struct Inner {
+    options: WgpuOptions,
+    ctx: egui::Context,
+    // Uninstalls the repaint callback along with the window.
+    _repaint: RepaintOnRequest,
+    gpu: Option<Gpu>,
+    ui: Option<UiFn>,
+    error: Option<RunError>,
+    #[cfg(feature = "persistence")]
+    storage: Option<Storage>,
+    #[cfg(feature = "persistence")]
+    on_save: Option<SaveFn>,
+    #[cfg(feature = "persistence")]
+    autosave: Option<app::TimeoutHandle>,
+}
+
+impl Inner {
+    #[cfg(feature = "persistence")]
+    fn save(&mut self) {
+        if let Some(storage) = &mut self.storage {
+            storage.save_memory(&self.ctx);
+            let geometry = self.gpu.as_mut().and_then(|gpu| {
+                // The last resize or hide may still be queued.
+                gpu.state.fuse_queue();
+                gpu.state.window_geometry()
+            });
+            if let Some(geometry) = geometry {
+                storage.set_string(GEOMETRY_KEY, geometry.to_string());
+            }
+            if let Some(gpu) = &self.gpu {
+                storage.set_value(VISUAL_SCALE_KEY, &gpu.state.visual_scale());
+            }
+            if let Some(on_save) = &mut self.on_save {
+                on_save(storage);
+            }
+            if let Err(err) = storage.flush() {
+                eprintln!("Failed to save {}: {}", storage.path().display(), err);
+            }
+        }
+    }
+}
+
+/// Save every `Storage::autosave_interval`, even while no frames are drawn.
+#[cfg(feature = "persistence")]
+fn autosave(inner: &std::rc::Weak<RefCell<Inner>>, handle: app::TimeoutHandle) {
+    // The window is gone, let the timeout expire.
+    let inner = match inner.upgrade() {
+        Some(inner) => inner,
+        None => return,
+    };
+    let interval = match inner.try_borrow_mut() {
+        Ok(mut inner) => {
+            inner.save();
+            match &inner.storage {
+                Some(storage) => storage.autosave_interval.as_secs_f64(),
+                None => return,
+            }
+        }
+        // Borrowed by a nested event loop running inside the ui closure, retry shortly.
+        Err(_) => 1.0,
+    };
+    app::repeat_timeout3(interval, handle);
+}
+
+/// FLTK window hosting egui, the handle and draw callbacks are installed automatically.
+///
+/// WGPU resources are created on the first draw, once the window has a native handle.
+/// Created while a group is open (e.g: a `Tile`), it becomes an embedded pane of that layout.
+#[derive(Clone)]
+pub struct EguiWindow {
+    window: AppWindow,
+    inner: Rc<RefCell<Inner>>,
+    /// A restored geometry was maximized, FLTK can only maximize once shown.
+    maximize_on_show: Rc<Cell<bool>>,
+}
+
+impl EguiWindow {
+    /// Create a window with the default `WgpuOptions`.
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> Self {
+        Self::with_options(x, y, w, h, label, WgpuOptions::default())
+    }
+
+    pub fn with_options(x: i32, y: i32, w: i32, h: i32, label: &str, options: WgpuOptions) -> Self {
+        let mut window = AppWindow::default().with_pos(x, y).with_size(w, h);
+        window.set_label(label);
+        window.end();
+
+        // Events are queued without borrowing the state, so none are lost while drawing.
+        let queue = EventQueue::new();
+        let ctx = egui::Context::default();
+        let repaint = repaint_on_request(&ctx, &window, &queue);
+
+        let inner = Rc::new(RefCell::new(Inner {
+            options,
+            ctx,
+            _repaint: repaint,
+            gpu: None,
+            ui: None,
+            error: None,
+            #[cfg(feature = "persistence")]
+            storage: None,
+            #[cfg(feature = "persistence")]
+            on_save: None,
+            #[cfg(feature = "persistence")]
+            autosave: None,
+        }));
+
+        let maximize_on_show = Rc::new(Cell::new(false));
+        window.handle({
+            let queue = queue.clone();
+            let maximize_on_show = maximize_on_show.clone();
+            move |win, event| {
+                if event == enums::Event::Show && maximize_on_show.take() {
+                    win.maximize();
+                }
+                queue.push(win, event)
+            }
+        });
+        // Follow the pane's geometry when embedded in a layout.
+        queue.track_resize(&mut window);
+
+        window.draw({
+            let inner = inner.clone();
+            move |window| {
+                let mut inner = inner.borrow_mut();
+                let inner = &mut *inner;
+                if inner.gpu.is_none() {
+                    match Gpu::new(window, &inner.options) {
+                        Ok(mut gpu) => {
+                            gpu.state.queue = queue.clone();
+                            inner.gpu = Some(gpu);
+                            #[cfg(feature = "persistence")]
+                            if let Some(storage) = &inner.storage {
+                                storage.load_memory(&inner.ctx);
+                                if let Some(scale) = storage.get_value::<f32>(VISUAL_SCALE_KEY) {
+                                    let gpu = inner.gpu.as_mut().unwrap();
+                                    gpu.state.set_relative_visual_scale(scale);
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to initialize egui window: {}", e);
+                            inner.error = Some(e);
+                            window.hide();
+                            return;
+                        }
+                    }
+                }
+                let gpu = inner.gpu.as_mut().unwrap();
+                gpu.painter.sync_surface(&gpu.device, window);
+                let state = &mut gpu.state;
+                gpu.painter.apply_render_scale(&gpu.device, state);
+                let input = state.take_input();
+                let mut frame = Frame {
+                    device: &gpu.device,
+                    queue: &gpu.queue,
+                    painter: &mut gpu.painter,
+                    state,
+                    window: &mut *window,
+                    #[cfg(feature = "persistence")]
+                    storage: inner.storage.as_mut(),
+                    close: false,
+                };
+
+                let ui = &mut inner.ui;
+                let app_output = inner.ctx.run(input, |ctx| {
+                    if let Some(ui) = ui {
+                        ui(ctx, &mut frame);
+                    }
+                });
+
+                let close = frame.close;
+                let state = &mut gpu.state;
+                if close || !state.window_visible() {
+                    // Not painted, but egui sends each texture delta only once.
+                    let textures_delta = app_output.textures_delta;
+                    gpu.painter
+                        .update_textures(&gpu.device, &gpu.queue, textures_delta);
+                    if close {
+                        window.hide();
+                    }
+                    return;
+                }
+
+                // Consume the resize flag, the repaint is already in progress.
+                state.window_resized();
+                state.fuse_output(window, app_output.platform_output);
+                let clipped_primitive = inner.ctx.tessellate(app_output.shapes);
+                gpu.painter.paint_jobs(
+                    &gpu.device,
+                    &gpu.queue,
+                    &state.screen_descriptor,
+                    clipped_primitive,
+                    app_output.textures_delta,
+                );
+                state.schedule_repaint(window, app_output.repaint_after);
+            }
+        });
+
+        Self {
+            window,
+            inner,
+            maximize_on_show,
+        }
+    }
+
+    /// Set the closure building the UI each frame.
+    pub fn set_ui<F>(&mut self, ui: F)
+    where
+        F: FnMut(&egui::Context, &mut Frame) + 'static,
+    {
+        self.inner.borrow_mut().ui = Some(Box::new(ui));
+        self.window.redraw();
+    }
+
+    /// Persist egui's memory into `storage`, it's restored on the first frame and autosaved.
+    /// Call it before `show()` so the saved window geometry is reapplied as well.
+    #[cfg(feature = "persistence")]
+    pub fn set_storage(&mut self, storage: Storage) {
+        if !self.window.shown() {
+            let geometry = storage
+                .get_string(GEOMETRY_KEY)
+                .and_then(|geometry| geometry.parse::<WindowGeometry>().ok());
+            if let Some(geometry) = geometry {
+                self.restore_geometry(&geometry);
+            }
+        }
+        let interval = storage.autosave_interval;
+        let mut inner = self.inner.borrow_mut();
+        inner.storage = Some(storage);
+        if inner.autosave.is_none() {
+            let weak = Rc::downgrade(&self.inner);
+            inner.autosave = Some(app::add_timeout3(interval.as_secs_f64(), move |handle| {
+                autosave(&weak, handle)
+            }));
+        }
+    }
+
+    /// Set the closure storing app state, called on autosave and `save`.
+    #[cfg(feature = "persistence")]
+    pub fn set_on_save<F>(&mut self, on_save: F)
+    where
+        F: FnMut(&mut Storage) + 'static,
+    {
+        self.inner.borrow_mut().on_save = Some(Box::new(on_save));
+    }
+
+    /// Save egui's memory and app state to disk now, e.g: on exit.
+    #[cfg(feature = "persistence")]
+    pub fn save(&mut self) {
+        self.inner.borrow_mut().save();
+    }
+
+    /// Current geometry of the window, the last recorded one once hidden.
+    pub fn window_geometry(&self) -> WindowGeometry {
+        let mut inner = self.inner.borrow_mut();
+        match inner.gpu.as_mut().and_then(|gpu| {
+            gpu.state.fuse_queue();
+            gpu.state.window_geometry()
+        }) {
+            Some(geometry) => geometry,
+            None => WindowGeometry::from_window(&self.window),
+        }
+    }
+
+    /// Apply a previously saved geometry, call it before `show()`.
+    pub fn restore_geometry(&mut self, geometry: &WindowGeometry) {
+        geometry.apply(&mut self.window);
+        self.maximize_on_show
+            .set(geometry.maximized && !geometry.fullscreen);
+    }
+
+    /// The egui context owned by the window.
+    pub fn context(&self) -> egui::Context {
+        self.inner.borrow().ctx.clone()
+    }
+
+    /// Take the error that prevented the WGPU resources from being created, if any.
+    pub fn take_error(&mut self) -> Option<RunError> {
+        self.inner.borrow_mut().error.take()
+    }
+}
+
+impl Deref for EguiWindow {
+    type Target = AppWindow;
+
+    fn deref(&self) -> &Self::Target {
+        &self.window
+    }
+}
+
+impl DerefMut for EguiWindow {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.window
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::VecDeque;
+
+/// Number of frame intervals kept in the history.
+const HISTORY_LEN: usize = 120;
+
+/// Intervals longer than this are idle gaps (reactive rendering), not frame times.
+const MAX_FRAME_DT: f32 = 0.25;
+
+/// Frame time statistics in seconds, see `EguiState::frame_stats`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FrameStats {
+    pub last: f32,
+    pub average: f32,
+    pub max: f32,
+}
+
+impl FrameStats {
+    /// Average frames per second.
+    pub fn fps(&self) -> f32 {
+        if self.average > 0.0 {
+            1.0 / self.average
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Measures real frame intervals and smooths them into egui's `predicted_dt`.
+pub(crate) struct FrameTimer {
+    last_frame: Option<f64>,
+    history: VecDeque<f32>,
+    predicted_dt: f32,
+}
+
+impl Default for FrameTimer {
+    fn default() -> Self {
+        Self {
+            last_frame: None,
+            history: VecDeque::with_capacity(HISTORY_LEN),
+            predicted_dt: 1.0 / 60.0,
+        }
+    }
+}
+
+impl FrameTimer {
+    /// Record the start of a frame at `now` (seconds), returns the smoothed predicted dt.
+    pub(crate) fn begin_frame(&mut self, now: f64) -> f32 {
+        if let Some(last) = self.last_frame {
+            let dt = (now - last).max(0.0) as f32;
+            if dt <= MAX_FRAME_DT {
+                if self.history.len() == HISTORY_LEN {
+                    self.history.pop_front();
+                }
+                self.history.push_back(dt);
+                self.predicted_dt = self.predicted_dt * 0.9 + dt * 0.1;
+            }
+        }
+        self.last_frame = Some(now);
+        self.predicted_dt
+    }
+
+    pub(crate) fn predicted_dt(&self) -> f32 {
+        self.predicted_dt
+    }
+
+    pub(crate) fn history(&self) -> impl Iterator<Item = f32> + '_ {
+        self.history.iter().copied()
+    }
+
+    pub(crate) fn stats(&self) -> FrameStats {
+        let last = self.history.back().copied().unwrap_or_default();
+        let max = self.history.iter().copied().fold(0.0, f32::max);
+        let average = if self.history.is_empty() {
+            0.0
+        } else {
+            self.history.iter().sum::<f32>() / self.history.len() as f32
+        };
+        FrameStats { last, average, max }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_of_empty_history() {
+        let timer = FrameTimer::default();
+        assert_eq!(timer.stats(), FrameStats::default());
+        assert_eq!(timer.stats().fps(), 0.0);
+    }
+
+    #[test]
+    fn stats() {
+        let mut timer = FrameTimer::default();
+        for now in [0.0, 0.01, 0.03, 0.06] {
+            timer.begin_frame(now);
+        }
+        let stats = timer.stats();
+        assert!((stats.last - 0.03).abs() < 1e-6);
+        assert!((stats.max - 0.03).abs() < 1e-6);
+        assert!((stats.average - 0.02).abs() < 1e-6);
+        assert!((stats.fps() - 50.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn idle_gaps_are_skipped() {
+        let mut timer = FrameTimer::default();
+        timer.begin_frame(0.0);
+        timer.begin_frame(0.02);
+        let predicted = timer.predicted_dt();
+        timer.begin_frame(5.0);
+        assert_eq!(timer.history().count(), 1);
+        assert_eq!(timer.predicted_dt(), predicted);
+        timer.begin_frame(5.01);
+        assert_eq!(timer.history().count(), 2);
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut timer = FrameTimer::default();
+        for i in 0..=HISTORY_LEN * 2 {
+            timer.begin_frame(i as f64 * 0.01);
+        }
+        assert_eq!(timer.history().count(), HISTORY_LEN);
+    }
+}
@@ -0,0 +1,273 @@
+use fltk::{
+    app,
+    prelude::{WidgetBase, WindowExt},
+    window,
+};
+use std::{fmt, str::FromStr};
+
+/// Position, size and screen of a window, saved on resize/move and reapplied before `show()`.
+///
+/// The rect is the normal (restored) one, a maximized or fullscreen window keeps the rect it had
+/// before. FLTK only maximizes shown windows, see `restore_maximized`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    pub screen: i32,
+    pub fullscreen: bool,
+    pub maximized: bool,
+}
+
+impl WindowGeometry {
+    /// Capture the current geometry of a shown window.
+    pub fn from_window<W>(win: &W) -> Self
+    where
+        W: WindowExt,
+    {
+        Self {
+            x: win.x(),
+            y: win.y(),
+            w: win.w(),
+            h: win.h(),
+            screen: win.screen_num(),
+            fullscreen: win.fullscreen_active(),
+            maximized: top_level(win).maximize_active(),
+        }
+    }
+
+    /// Update from the window, the normal (restored) rect is kept while maximized or fullscreen.
+    pub fn update<W>(&mut self, win: &W)
+    where
+        W: WindowExt,
+    {
+        self.merge(Self::from_window(win));
+    }
+
+    pub(crate) fn merge(&mut self, captured: Self) {
+        self.screen = captured.screen;
+        self.fullscreen = captured.fullscreen;
+        self.maximized = captured.maximized;
+        if !self.fullscreen && !self.maximized {
+            self.x = captured.x;
+            self.y = captured.y;
+            self.w = captured.w;
+            self.h = captured.h;
+        }
+    }
+
+    /// Apply to a window before `show()`, clamped to the screens that currently exist.
+    /// Follow with `restore_maximized` once shown.
+    pub fn apply<W>(&self, win: &mut W)
+    where
+        W: WindowExt,
+    {
+        let screen = self.screen_or_primary(app::screen_count());
+        let (x, y, w, h) = self.clamp_to(screen, app::screen_work_area(screen));
+        win.resize(x, y, w, h);
+        if self.fullscreen {
+            win.fullscreen(true);
+        }
+    }
+
+    /// Maximize the window if it was saved maximized, call it once the window is shown.
+    pub fn restore_maximized<W>(&self, win: &mut W)
+    where
+        W: WindowExt,
+    {
+        if self.maximized && !self.fullscreen && win.shown() {
+            top_level(win).maximize();
+        }
+    }
+
+    fn screen_or_primary(&self, screen_count: i32) -> i32 {
+        if self.screen >= 0 && self.screen < screen_count {
+            self.screen
+        } else {
+            0
+        }
+    }
+
+    /// Fit the rect into the work area of `screen`.
+    fn clamp_to(&self, screen: i32, work_area: (i32, i32, i32, i32)) -> (i32, i32, i32, i32) {
+        let (sx, sy, sw, sh) = work_area;
+        let w = self.w.clamp(1, sw.max(1));
+        let h = self.h.clamp(1, sh.max(1));
+        if screen == self.screen {
+            (
+                self.x.clamp(sx, sx + sw.max(w) - w),
+                self.y.clamp(sy, sy + sh.max(h) - h),
+                w,
+                h,
+            )
+        } else {
+            // The saved screen is gone, center on the primary one.
+            (sx + (sw - w) / 2, sy + (sh - h) / 2, w, h)
+        }
+    }
+}
+
+/// The maximize methods aren't part of `WindowExt`, every window type shares `Fl_Window`'s.
+fn top_level<W>(win: &W) -> window::Window
+where
+    W: WindowExt,
+{
+    unsafe { window::Window::from_widget_ptr(win.as_widget_ptr()) }
+}
+
+impl fmt::Display for WindowGeometry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {} {}",
+            self.x, self.y, self.w, self.h, self.screen, self.fullscreen, self.maximized
+        )
+    }
+}
+
+impl FromStr for WindowGeometry {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        // Geometries saved before the maximized flag existed have 6 fields.
+        if parts.len() != 6 && parts.len() != 7 {
+            return Err(format!("invalid window geometry: {:?}", s));
+        }
+        let int = |i: usize| parts[i].parse::<i32>().map_err(|e| e.to_string());
+        let flag = |i: usize| parts[i].parse::<bool>().map_err(|e| e.to_string());
+        Ok(Self {
+            x: int(0)?,
+            y: int(1)?,
+            w: int(2)?,
+            h: int(3)?,
+            screen: int(4)?,
+            fullscreen: flag(5)?,
+            maximized: match parts.len() {
+                7 => flag(6)?,
+                _ => false,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GEOMETRY: WindowGeometry = WindowGeometry {
+        x: 10,
+        y: -20,
+        w: 800,
+        h: 600,
+        screen: 1,
+        fullscreen: false,
+        maximized: false,
+    };
+
+    #[test]
+    fn round_trip() {
+        let saved = GEOMETRY.to_string();
+        assert_eq!(saved, "10 -20 800 600 1 false false");
+        assert_eq!(saved.parse::<WindowGeometry>(), Ok(GEOMETRY));
+        let maximized = WindowGeometry {
+            maximized: true,
+            ..GEOMETRY
+        };
+        assert_eq!(
+            maximized.to_string().parse::<WindowGeometry>(),
+            Ok(maximized)
+        );
+    }
+
+    #[test]
+    fn older_strings_are_not_maximized() {
+        assert_eq!(
+            "10 -20 800 600 1 false".parse::<WindowGeometry>(),
+            Ok(GEOMETRY)
+        );
+    }
+
+    #[test]
+    fn invalid_strings() {
+        assert!("".parse::<WindowGeometry>().is_err());
+        assert!("10 20 800 600 1".parse::<WindowGeometry>().is_err());
+        assert!("10 20 800 600 1 false 0".parse::<WindowGeometry>().is_err());
+        assert!("10 20 800 600 1 false false 0"
+            .parse::<WindowGeometry>()
+            .is_err());
+        assert!("10 20 wide 600 1 false".parse::<WindowGeometry>().is_err());
+        assert!("10 20 800 600 1 yes".parse::<WindowGeometry>().is_err());
+    }
+
+    #[test]
+    fn merge_keeps_normal_rect_while_fullscreen() {
+        let mut geometry = GEOMETRY;
+        geometry.merge(WindowGeometry {
+            x: 0,
+            y: 0,
+            w: 1920,
+            h: 1080,
+            screen: 0,
+            fullscreen: true,
+            maximized: false,
+        });
+        assert_eq!(
+            geometry,
+            WindowGeometry {
+                screen: 0,
+                fullscreen: true,
+                ..GEOMETRY
+            }
+        );
+    }
+
+    #[test]
+    fn merge_keeps_normal_rect_while_maximized() {
+        let mut geometry = GEOMETRY;
+        geometry.merge(WindowGeometry {
+            x: 0,
+            y: 0,
+            w: 1920,
+            h: 1040,
+            screen: 1,
+            fullscreen: false,
+            maximized: true,
+        });
+        assert_eq!(
+            geometry,
+            WindowGeometry {
+                maximized: true,
+                ..GEOMETRY
+            }
+        );
+    }
+
+    #[test]
+    fn missing_screen_falls_back_to_primary() {
+        assert_eq!(GEOMETRY.screen_or_primary(2), 1);
+        assert_eq!(GEOMETRY.screen_or_primary(1), 0);
+        let negative = WindowGeometry {
+            screen: -1,
+            ..GEOMETRY
+        };
+        assert_eq!(negative.screen_or_primary(2), 0);
+    }
+
+    #[test]
+    fn clamp_to_work_area() {
+        // Partly off screen, pulled back inside.
+        assert_eq!(GEOMETRY.clamp_to(1, (0, 0, 1920, 1080)), (10, 0, 800, 600));
+        // Larger than the work area, shrunk to it.
+        assert_eq!(
+            GEOMETRY.clamp_to(1, (100, 50, 640, 480)),
+            (100, 50, 640, 480)
+        );
+        // On another screen, centered.
+        assert_eq!(
+            GEOMETRY.clamp_to(0, (0, 0, 1000, 800)),
+            (100, 100, 800, 600)
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use crate::{modal::release_modal_window, DragPayload, WindowGeometry, PPU};
+use fltk::{
+    app, enums,
+    prelude::{WidgetBase, WindowExt},
+};
+use std::{
+    cell::{Cell, RefCell, RefMut},
+    rc::Rc,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Mirrors `app::keyboard_screen_scaling`, FLTK has no getter for it.
+static KEYBOARD_SCALING: AtomicBool = AtomicBool::new(true);
+
+pub(crate) fn set_keyboard_scaling(enabled: bool) {
+    KEYBOARD_SCALING.store(enabled, Ordering::Relaxed);
+}
+
+/// Ctrl/Cmd with `+`, `=`, `-` or `0`, FLTK's screen scaling shortcut.
+fn is_scaling_shortcut(key: enums::Key, key_state: enums::EventState) -> bool {
+    if key_state & enums::EventState::Command != enums::EventState::Command {
+        return false;
+    }
+    ['+', '=', '-', '0']
+        .iter()
+        .any(|&c| key == enums::Key::from_char(c) || key.bits() == enums::Key::KP.bits() + c as i32)
+}
+
+/// Snapshot of an FLTK event and the event state egui needs, taken while the event is dispatched.
+pub(crate) struct FltkEvent {
+    pub(crate) event: enums::Event,
+    pub(crate) coords: (i32, i32),
+    pub(crate) mouse_button: app::MouseButton,
+    pub(crate) key: enums::Key,
+    pub(crate) key_state: enums::EventState,
+    /// Composed text of a key press, if any.
+    pub(crate) text: Option<String>,
+    pub(crate) ctrl: bool,
+    pub(crate) wheel_dy: app::MouseWheel,
+    pub(crate) win_size: (i32, i32),
+    /// Physical size of the window.
+    pub(crate) pixel_size: (i32, i32),
+    pub(crate) screen_num: i32,
+    pub(crate) ppu: f32,
+    pub(crate) geometry: Option<WindowGeometry>,
+    /// A native drag of the `DragPayload` was started by this event.
+    pub(crate) dnd_started: bool,
+}
+
+impl FltkEvent {
+    pub(crate) fn capture<W>(win: &W, event: enums::Event) -> Self
+    where
+        W: WindowExt + PPU,
+    {
+        let text = if event == enums::Event::KeyDown {
+            match (app::event_text().chars().next(), app::compose()) {
+                (Some(c), Some(del)) => {
+                    if del != 0 {
+                        app::compose_reset();
+                    }
+                    Some(c.to_string())
+                }
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        // Panes embedded in a layout have no geometry worth saving.
+        let embedded = win.parent().is_some();
+        let geometry = match event {
+            enums::Event::Resize | enums::Event::Hide if win.shown() && !embedded => {
+                Some(WindowGeometry::from_window(win))
+            }
+            _ => None,
+        };
+
+        let coords = if embedded {
+            // Events of a child window may be relative to an ancestor (grabs, pushed widgets),
+            // screen coordinates map them into the pane either way.
+            (
+                app::event_x_root() - win.x_root(),
+                app::event_y_root() - win.y_root(),
+            )
+        } else {
+            app::event_coords()
+        };
+
+        Self {
+            event,
+            coords,
+            mouse_button: app::event_mouse_button(),
+            key: app::event_key(),
+            key_state: app::event_state(),
+            text,
+            ctrl: app::is_event_ctrl(),
+            wheel_dy: app::event_dy(),
+            win_size: (win.width(), win.height()),
+            pixel_size: (win.pixel_w(), win.pixel_h()),
+            screen_num: win.screen_num(),
+            ppu: win.pixels_per_unit(),
+            geometry,
+            dnd_started: false,
+        }
+    }
+
+    /// A drag that left the window.
+    fn drags_out(&self) -> bool {
+        let (x, y) = self.coords;
+        let (w, h) = self.win_size;
+        self.event == enums::Event::Drag && (x < 0 || y < 0 || x >= w || y >= h)
+    }
+}
+
+/// Events egui is interested in.
+pub(crate) fn is_egui_event(event: enums::Event) -> bool {
+    matches!(
+        event,
+        enums::Event::Push
+            | enums::Event::Released
+            | enums::Event::KeyDown
+            | enums::Event::KeyUp
+            | enums::Event::MouseWheel
+            | enums::Event::Resize
+            | enums::Event::Move
+            | enums::Event::Drag
+            | enums::Event::Focus
+            | enums::Event::Unfocus
+            | enums::Event::Leave
+            | enums::Event::Hide
+            | enums::Event::Show
+    )
+}
+
+/// Pending repaint wakeups of an `EguiState`, stopped as soon as the window is hidden.
+#[derive(Default)]
+pub(crate) struct RepaintHandles {
+    pub(crate) timeout: Option<app::TimeoutHandle>,
+    pub(crate) idle: Option<app::IdleHandle>,
+    pub(crate) ticker: Option<app::TimeoutHandle>,
+}
+
+impl RepaintHandles {
+    pub(crate) fn cancel_timeout(&mut self) {
+        if let Some(handle) = self.timeout.take() {
+            if app::has_timeout3(handle) {
+                app::remove_timeout3(handle);
+            }
+        }
+    }
+
+    /// Stop the idle callback of `RenderMode::Continuous` and the ticker of `RenderMode::FpsCapped`.
+    pub(crate) fn stop_continuous(&mut self) {
+        if let Some(handle) = self.idle.take() {
+            app::remove_idle3(handle);
+        }
+        if let Some(handle) = self.ticker.take() {
+            if app::has_timeout3(handle) {
+                app::remove_timeout3(handle);
+            }
+        }
+    }
+}
+
+struct QueueInner {
+    events: RefCell<Vec<FltkEvent>>,
+    repaint: RefCell<RepaintHandles>,
+    redraw_on_input: Cell<bool>,
+    sync_resize: Cell<bool>,
+    /// Between `take_input` and `fuse_output` of the draw callback.
+    drawing: Cell<bool>,
+    drag_payload: RefCell<Option<DragPayload>>,
+}
+
+/// Shared input queue of an `EguiState`, pushing never borrows the state so no event is dropped
+/// while the draw callback holds it. Queued events are fused on the next `take_input`.
+#[derive(Clone)]
+pub struct EventQueue {
+    inner: Rc<QueueInner>,
+}
+
+impl EventQueue {
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: Rc::new(QueueInner {
+                events: RefCell::new(Vec::new()),
+                repaint: RefCell::new(RepaintHandles::default()),
+                redraw_on_input: Cell::new(true),
+                sync_resize: Cell::new(true),
+                drawing: Cell::new(false),
+                drag_payload: RefCell::new(None),
+            }),
+        }
+    }
+
+    /// Queue an event from the window's handle callback, returns whether it should be consumed.
+    pub fn push<W>(&self, win: &mut W, event: enums::Event) -> bool
+    where
+        W: WindowExt + PPU,
+    {
+        if !is_egui_event(event) {
+            return false;
+        }
+        if event == enums::Event::Push && win.parent().is_some() {
+            // Clicking a pane gives it the keyboard.
+            let _ = win.take_focus();
+        }
+        let mut captured = FltkEvent::capture(win, event);
+        let payload = if captured.drags_out() {
+            self.inner.drag_payload.borrow_mut().take()
+        } else {
+            None
+        };
+        captured.dnd_started = payload.is_some();
+        // Unconsumed, the key down comes back as a shortcut FLTK rescales the app on.
+        let scaling = event == enums::Event::KeyDown
+            && KEYBOARD_SCALING.load(Ordering::Relaxed)
+            && is_scaling_shortcut(captured.key, captured.key_state);
+        if event == enums::Event::Released {
+            self.inner.drag_payload.borrow_mut().take();
+        }
+        if event == enums::Event::Hide {
+            // A hidden window doesn't draw, so the queue isn't drained until it's shown again:
+            // release the app and stop repainting now.
+            release_modal_window(win);
+            let mut repaint = self.inner.repaint.borrow_mut();
+            repaint.cancel_timeout();
+            repaint.stop_continuous();
+        }
+        self.inner.events.borrow_mut().push(captured);
+        // Flushing while drawing would re-enter the draw callback, e.g: a pane resized by the UI.
+        if event == enums::Event::Resize
+            && self.inner.sync_resize.get()
+            && !self.inner.drawing.get()
+            && win.shown()
+            && win.pixel_w() > 0
+            && win.pixel_h() > 0
+        {
+            // Draw at the new size right away, so the UI follows the window edge while resizing.
+            win.flush();
+        } else if self.inner.redraw_on_input.get() || event == enums::Event::Show {
+            win.redraw();
+        }
+        if let Some(payload) = payload {
+            // Runs a nested event loop, started here as no state is borrowed by the handle callback.
+            payload.start_dnd();
+        }
+        // Track visibility without consuming the event.
+        !scaling && !matches!(event, enums::Event::Hide | enums::Event::Show)
+    }
+
+    /// Queue a resize whenever `win` is resized as a child of a layout (`Group`, `Tile`, `Scroll`),
+    /// only top-level windows get `Event::Resize`. Replaces the window's resize callback.
+    pub fn track_resize<W>(&self, win: &mut W)
+    where
+        W: WindowExt + WidgetBase + PPU,
+    {
+        let queue = self.clone();
+        win.resize_callback(move |win, _, _, _, _| {
+            if win.parent().is_some() {
+                queue.push(win, enums::Event::Resize);
+            }
+        });
+    }
+
+    /// Draw synchronously on resize (default), a resize while drawing only requests a redraw.
+    pub fn set_sync_resize(&self, sync: bool) {
+        self.inner.sync_resize.set(sync);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.events.borrow().is_empty()
+    }
+
+    pub(crate) fn set_drag_payload(&self, payload: Option<DragPayload>) {
+        *self.inner.drag_payload.borrow_mut() = payload;
+    }
+
+    pub(crate) fn drag_payload(&self) -> Option<DragPayload> {
+        self.inner.drag_payload.borrow().clone()
+    }
+
+    pub(crate) fn repaint(&self) -> RefMut<'_, RepaintHandles> {
+        self.inner.repaint.borrow_mut()
+    }
+
+    pub(crate) fn drain(&self) -> Vec<FltkEvent> {
+        std::mem::take(&mut *self.inner.events.borrow_mut())
+    }
+
+    pub(crate) fn set_drawing(&self, drawing: bool) {
+        self.inner.drawing.set(drawing);
+    }
+
+    pub(crate) fn set_redraw_on_input(&self, redraw: bool) {
+        self.inner.redraw_on_input.set(redraw);
+    }
+
+    pub(crate) fn redraw_on_input(&self) -> bool {
+        self.inner.redraw_on_input.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaling_shortcuts() {
+        let command = enums::EventState::Command;
+        for c in ['+', '=', '-', '0'] {
+            assert!(is_scaling_shortcut(enums::Key::from_char(c), command));
+            let keypad = enums::Key::from_i32(enums::Key::KP.bits() + c as i32);
+            assert!(is_scaling_shortcut(
+                keypad,
+                command | enums::EventState::Shift
+            ));
+            assert!(!is_scaling_shortcut(
+                enums::Key::from_char(c),
+                enums::EventState::None
+            ));
+        }
+        assert!(!is_scaling_shortcut(enums::Key::from_char('c'), command));
+        assert!(!is_scaling_shortcut(enums::Key::from_char('1'), command));
+    }
+}
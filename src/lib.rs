@@ -10,31 +10,103 @@ use fltk::{
     prelude::{FltkError, ImageExt, WindowExt},
 };
 pub use pollster;
-use std::time::Instant;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 pub use wgpu;
+mod channel;
+pub use channel::{message_channel, MessageReceiver, MessageSender};
 mod clipboard;
 mod egui_image;
 use clipboard::Clipboard;
-
-/// Pixel per unit trait helper.
+mod dnd;
+pub use dnd::DragPayload;
+mod egui_window;
+pub use egui_window::{AppWindow, EguiWindow, Frame, RunError, WgpuOptions};
+#[cfg(feature = "persistence")]
+mod persistence;
+#[cfg(feature = "persistence")]
+pub use persistence::Storage;
+mod run;
+pub use run::{run, App, NativeOptions};
+mod clock;
+pub use clock::{Clock, FixedStepClock, ManualClock, RealClock};
+mod geometry;
+pub use geometry::WindowGeometry;
+mod frame_timing;
+pub use frame_timing::FrameStats;
+use frame_timing::FrameTimer;
+mod input_queue;
+pub use input_queue::EventQueue;
+use input_queue::FltkEvent;
+mod modal;
+use modal::{release_modal_window, set_modal_window};
+mod render_scale;
+use render_scale::Resampler;
+mod threaded;
+pub use threaded::{FrameOutput, ThreadedUi};
+mod widgets;
+pub use widgets::{WidgetEntry, WidgetEventKind, WidgetList};
+
+/// Pixel per unit trait helper, forwards to the window type's own methods.
 pub trait PPU {
     fn pixels_per_unit(&self) -> f32;
+
+    /// Width in physical pixels.
+    fn pixel_w(&self) -> i32;
+
+    /// Height in physical pixels.
+    fn pixel_h(&self) -> i32;
 }
 
-impl PPU for fltk::window::Window {
-    fn pixels_per_unit(&self) -> f32 {
-        self.pixels_per_unit()
-    }
+macro_rules! impl_ppu {
+    ($($(#[$attr:meta])* $window:ty),* $(,)?) => {
+        $(
+            $(#[$attr])*
+            impl PPU for $window {
+                fn pixels_per_unit(&self) -> f32 {
+                    <$window>::pixels_per_unit(self)
+                }
+
+                fn pixel_w(&self) -> i32 {
+                    <$window>::pixel_w(self)
+                }
+
+                fn pixel_h(&self) -> i32 {
+                    <$window>::pixel_h(self)
+                }
+            }
+        )*
+    };
 }
 
-#[cfg(feature = "enable-glwindow")]
-impl PPU for fltk::window::GlWindow {
-    fn pixels_per_unit(&self) -> f32 {
-        self.pixels_per_unit()
-    }
+impl_ppu!(
+    fltk::window::SingleWindow,
+    fltk::window::DoubleWindow,
+    fltk::window::OverlayWindow,
+    #[cfg(feature = "enable-glwindow")]
+    fltk::window::GlWindow,
+);
+
+/// Opt out of FLTK's screen scaling: force scale 1.0 on the window's screen and disable
+/// Ctrl+Plus/Minus/0, this affects every FLTK widget of the process. Call it before `begin_with`.
+pub fn disable_screen_scaling<W>(window: &W)
+where
+    W: WindowExt,
+{
+    app::set_screen_scale(window.screen_num(), 1.0);
+    app::keyboard_screen_scaling(false);
+    input_queue::set_keyboard_scaling(false);
 }
 
 /// Construct the frontend.
+///
+/// FLTK's screen scale (`FLTK_SCALING_FACTOR`, desktop scaling, Ctrl+Plus/Minus/0) is folded into
+/// egui's `pixels_per_point`, see `disable_screen_scaling` to opt out.
 pub fn begin_with<'a, W>(
     window: &mut W,
     render_pass: RenderPass<'a>,
@@ -44,14 +116,6 @@ pub fn begin_with<'a, W>(
 where
     W: WindowExt + PPU,
 {
-    app::set_screen_scale(window.screen_num(), 1.0);
-    app::keyboard_screen_scaling(false);
-    let ppu = window.pixels_per_unit();
-    let x = window.width();
-    let y = window.height();
-    let rect = egui::vec2(x as _, y as _) / ppu;
-    let screen_rect = egui::Rect::from_min_size(egui::Pos2::new(0f32, 0f32), rect);
-
     let painter = Painter {
         render_pass,
         surface,
@@ -59,27 +123,13 @@ where
         encoder: wgpu::CommandEncoderDescriptor {
             label: Some("encoder"),
         },
+        instance: None,
+        native_handle: window.raw_handle(),
+        render_scale: 1.0,
+        resampler: None,
+        regions: Vec::new(),
     };
-
-    let state = EguiState {
-        _window_resized: false,
-        fuse_cursor: FusedCursor::new(),
-        pointer_pos: egui::Pos2::new(0.0, 0.0),
-        input: egui::RawInput {
-            screen_rect: Some(screen_rect),
-            pixels_per_point: Some(ppu),
-            ..Default::default()
-        },
-        clipboard: clipboard::Clipboard::default(),
-        _mouse_btn_pressed: false,
-        scroll_factor: 12.0,
-        zoom_factor: 8.0,
-        screen_descriptor: ScreenDescriptor {
-            size_in_pixels: [x as _, y as _],
-            pixels_per_point: ppu,
-        },
-    };
-    (painter, state)
+    (painter, EguiState::new(window))
 }
 
 pub struct Painter<'a> {
@@ -87,9 +137,87 @@ pub struct Painter<'a> {
     pub surface: wgpu::Surface,
     pub surface_config: wgpu::SurfaceConfiguration,
     encoder: wgpu::CommandEncoderDescriptor<'a>,
+    instance: Option<wgpu::Instance>,
+    native_handle: fltk::window::RawHandle,
+    render_scale: f32,
+    resampler: Option<Resampler>,
+    regions: Vec<RenderPass<'a>>,
 }
 
 impl<'a> Painter<'a> {
+    /// Hand over the instance the surface was created from, needed by `sync_surface`.
+    pub fn set_instance(&mut self, instance: wgpu::Instance) {
+        self.instance = Some(instance);
+    }
+
+    /// Render egui at `scale` times the surface resolution and resample it to the surface,
+    /// e.g: 2.0 supersamples thin lines on low-DPI screens, 0.5 saves fill rate on weak GPUs.
+    /// Call `apply_render_scale` each frame so egui tessellates at that resolution.
+    ///
+    /// Clamped to 0.25..=2.0, the resample is a single bilinear tap which aliases above 2x.
+    /// Default is 1.0 (render straight into the surface).
+    pub fn set_render_scale(&mut self, scale: f32) {
+        self.render_scale = if scale.is_finite() {
+            scale.clamp(0.25, 2.0)
+        } else {
+            1.0
+        };
+        if self.render_scale == 1.0 {
+            // Release the offscreen texture.
+            self.resampler = None;
+        }
+    }
+
+    pub fn render_scale(&self) -> f32 {
+        self.render_scale
+    }
+
+    /// Pass the scale egui is rendered at to `state`, call it before `EguiState::take_input`
+    /// (for every region's state). That's `render_scale`, lowered while the offscreen target
+    /// would exceed the device's texture size limit, see `effective_render_scale`.
+    pub fn apply_render_scale(&self, device: &wgpu::Device, state: &mut EguiState) {
+        // A queued resize changes the size the scale is limited for.
+        state.fuse_queue();
+        let (w, h) = state.pixel_size;
+        let size = [w.max(1) as u32, h.max(1) as u32];
+        state.render_scale = self.effective_render_scale(device, size);
+    }
+
+    /// `render_scale`, reduced so the offscreen target of a window of `size` physical pixels
+    /// stays within the texture size limit.
+    pub fn effective_render_scale(&self, device: &wgpu::Device, size: [u32; 2]) -> f32 {
+        if self.render_scale == 1.0 {
+            return 1.0;
+        }
+        let max = device.limits().max_texture_dimension_2d as f32;
+        self.render_scale
+            .min(max / size[0] as f32)
+            .min(max / size[1] as f32)
+    }
+
+    /// Rebuild the surface if the window got a new native handle (re-shown or re-parented),
+    /// the render pass and its textures are kept. Returns whether the surface was recreated.
+    pub fn sync_surface<W>(&mut self, device: &wgpu::Device, win: &W) -> bool
+    where
+        W: WindowExt + RWHandleExt,
+    {
+        let native_handle = win.raw_handle();
+        if native_handle == self.native_handle || !win.shown() {
+            return false;
+        }
+        let instance = match &self.instance {
+            Some(instance) => instance,
+            None => {
+                eprintln!("Native window handle changed, but no wgpu::Instance was set");
+                return false;
+            }
+        };
+        self.surface = unsafe { instance.create_surface(&win.use_compat()) };
+        self.surface.configure(device, &self.surface_config);
+        self.native_handle = native_handle;
+        true
+    }
+
     /// Paint with egui renderpass
     pub fn paint_with_rpass<'rpass>(
         &'rpass mut self,
@@ -100,15 +228,7 @@ impl<'a> Painter<'a> {
         clipped_primitive: Vec<egui::ClippedPrimitive>,
         texture: egui::TexturesDelta,
     ) {
-        if texture.free.len() > 0 {
-            texture.free.into_iter().for_each(|id| {
-                self.render_pass.free_texture(&id);
-            });
-        }
-
-        for (id, img_del) in texture.set {
-            self.render_pass.update_texture(device, queue, id, &img_del);
-        }
+        self.update_textures(device, queue, texture);
 
         self.render_pass
             .update_buffers(device, queue, &clipped_primitive, screen_descriptor);
@@ -117,6 +237,7 @@ impl<'a> Painter<'a> {
             .execute_with_renderpass(rpass, clipped_primitive, screen_descriptor);
     }
 
+    /// Paint into the surface, skipped if the window is minimized or hidden (zero sized).
     pub fn paint_jobs(
         &mut self,
         device: &wgpu::Device,
@@ -125,11 +246,10 @@ impl<'a> Painter<'a> {
         clipped_primitive: Vec<egui::ClippedPrimitive>,
         texture: egui::TexturesDelta,
     ) {
-        {
-            let size = screen_descriptor.size_in_pixels;
-            self.surface_config.width = size[0];
-            self.surface_config.height = size[1];
-            self.surface.configure(device, &self.surface_config);
+        // Textures are updated even if nothing is presented, egui sends each delta only once.
+        self.update_textures(device, queue, texture);
+        if !self.configure_surface(device, screen_descriptor.size_in_pixels) {
+            return;
         }
 
         // Record all render passes.
@@ -137,31 +257,42 @@ impl<'a> Painter<'a> {
             Ok(frame) => {
                 let mut encoder = device.create_command_encoder(&self.encoder);
 
-                if texture.free.len() > 0 {
-                    texture.free.into_iter().for_each(|id| {
-                        self.render_pass.free_texture(&id);
-                    });
+                let frame_view = frame.texture.create_view(&self.render_pass.tex_view_desc);
+                if self.render_scale == 1.0 {
+                    self.render_pass.update_buffers(
+                        device,
+                        queue,
+                        &clipped_primitive,
+                        screen_descriptor,
+                    );
+
+                    self.render_pass.execute(
+                        &mut encoder,
+                        &frame_view,
+                        clipped_primitive,
+                        screen_descriptor,
+                        Some(wgpu::Color::BLACK),
+                    );
+                } else {
+                    let scale =
+                        self.effective_render_scale(device, screen_descriptor.size_in_pixels);
+                    let scaled = scale_descriptor(screen_descriptor, scale);
+                    self.ensure_resampler(device);
+                    let resampler = self.resampler.as_mut().unwrap();
+
+                    self.render_pass
+                        .update_buffers(device, queue, &clipped_primitive, &scaled);
+
+                    self.render_pass.execute(
+                        &mut encoder,
+                        resampler.target_view(device, scaled.size_in_pixels),
+                        clipped_primitive,
+                        &scaled,
+                        Some(wgpu::Color::BLACK),
+                    );
+                    resampler.resample(&mut encoder, &frame_view);
                 }
 
-                for (id, img_del) in texture.set {
-                    self.render_pass.update_texture(device, queue, id, &img_del);
-                }
-
-                self.render_pass.update_buffers(
-                    device,
-                    queue,
-                    &clipped_primitive,
-                    screen_descriptor,
-                );
-
-                self.render_pass.execute(
-                    &mut encoder,
-                    &frame.texture.create_view(&self.render_pass.tex_view_desc),
-                    clipped_primitive,
-                    screen_descriptor,
-                    Some(wgpu::Color::BLACK),
-                );
-
                 // Submit command buffer
                 let cm_buffer = encoder.finish();
                 queue.submit(Some(cm_buffer));
@@ -173,6 +304,237 @@ impl<'a> Painter<'a> {
         // Draw finalize frame
         output_frame.present();
     }
+
+    /// Apply egui's texture updates to `render_pass`, also for frames that aren't painted
+    /// (hidden or minimized window): each delta is sent only once.
+    pub fn update_textures(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        textures_delta: egui::TexturesDelta,
+    ) {
+        apply_textures(&mut self.render_pass, device, queue, textures_delta);
+    }
+
+    /// Add a render pass for another egui context sharing the surface, contexts don't share
+    /// textures. Returns the index for `RegionPaint::pass`, 0 is `render_pass`.
+    pub fn add_region(&mut self, device: &wgpu::Device) -> usize {
+        self.regions
+            .push(RenderPass::new(device, self.surface_config.format, 1));
+        self.regions.len()
+    }
+
+    /// Render pass of a region, e.g: to register user textures of its context.
+    pub fn region_pass_mut(&mut self, pass: usize) -> Option<&mut RenderPass<'a>> {
+        match pass {
+            0 => Some(&mut self.render_pass),
+            i => self.regions.get_mut(i - 1),
+        }
+    }
+
+    /// Composite several egui contexts into the surface in one submission, each into its own
+    /// viewport and scissor. `size_in_pixels` is the physical size of the window, every region
+    /// needs its own pass (see `add_region`).
+    pub fn paint_regions(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        size_in_pixels: [u32; 2],
+        mut regions: Vec<RegionPaint>,
+    ) {
+        // Textures are updated even if nothing is presented, egui sends each delta only once.
+        for region in &mut regions {
+            let textures_delta = std::mem::take(&mut region.textures_delta);
+            if let Some(pass) = self.region_pass_mut(region.pass) {
+                apply_textures(pass, device, queue, textures_delta);
+            }
+        }
+        if !self.configure_surface(device, size_in_pixels) {
+            return;
+        }
+        let frame = match self.surface.get_current_texture() {
+            Ok(frame) => frame,
+            Err(e) => return eprintln!("Dropped frame with error: {}", e),
+        };
+        let mut encoder = device.create_command_encoder(&self.encoder);
+        let frame_view = frame.texture.create_view(&self.render_pass.tex_view_desc);
+
+        let scale = self.effective_render_scale(device, size_in_pixels);
+        let target_size = scale_size(size_in_pixels, scale);
+
+        let mut jobs = Vec::with_capacity(regions.len());
+        for region in regions {
+            let pass = match self.region_pass_mut(region.pass) {
+                Some(pass) => pass,
+                None => {
+                    eprintln!("No render pass for region {}", region.pass);
+                    continue;
+                }
+            };
+            let size = region.screen_descriptor.size_in_pixels;
+            if size[0] == 0 || size[1] == 0 {
+                continue;
+            }
+            let scaled = scale_descriptor(&region.screen_descriptor, scale);
+            pass.update_buffers(device, queue, &region.clipped_primitives, &scaled);
+            let origin = scale_size(region.origin, scale);
+            jobs.push((region.pass, origin, scaled, region.clipped_primitives));
+        }
+
+        {
+            let view = if scale == 1.0 {
+                &frame_view
+            } else {
+                self.ensure_resampler(device);
+                // Disjoint from the render passes borrowed below.
+                self.resampler
+                    .as_mut()
+                    .unwrap()
+                    .target_view(device, target_size)
+            };
+            let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("egui_regions"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            });
+            for (pass, origin, scaled, clipped_primitives) in jobs {
+                let pass = match pass {
+                    0 => &self.render_pass,
+                    i => &self.regions[i - 1],
+                };
+                pass.execute_with_renderpass_at(
+                    &mut rpass,
+                    clipped_primitives,
+                    &scaled,
+                    origin,
+                    target_size,
+                );
+            }
+        }
+        if let Some(resampler) = self.resampler.as_ref().filter(|_| scale != 1.0) {
+            resampler.resample(&mut encoder, &frame_view);
+        }
+
+        queue.submit(Some(encoder.finish()));
+        frame.present();
+    }
+
+    /// Reconfigure the surface if the size changed (e.g: during live resize),
+    /// false if there's nothing to present.
+    fn configure_surface(&mut self, device: &wgpu::Device, size: [u32; 2]) -> bool {
+        if size[0] == 0 || size[1] == 0 {
+            // Nothing to present, don't acquire a frame.
+            return false;
+        }
+        if self.surface_config.width != size[0] || self.surface_config.height != size[1] {
+            self.surface_config.width = size[0];
+            self.surface_config.height = size[1];
+            self.surface.configure(device, &self.surface_config);
+        }
+        true
+    }
+
+    fn ensure_resampler(&mut self, device: &wgpu::Device) {
+        let format = self.surface_config.format;
+        if self.resampler.as_ref().map(|r| r.format()) != Some(format) {
+            self.resampler = Some(Resampler::new(device, format));
+        }
+    }
+}
+
+/// Output of one egui context for `Painter::paint_regions`, see `EguiState::region_paint`.
+pub struct RegionPaint {
+    /// Render pass of the region, 0 is `Painter::render_pass`, others come from `Painter::add_region`.
+    pub pass: usize,
+    /// Top-left corner of the region in physical pixels.
+    pub origin: [u32; 2],
+    pub screen_descriptor: ScreenDescriptor,
+    pub clipped_primitives: Vec<egui::ClippedPrimitive>,
+    pub textures_delta: egui::TexturesDelta,
+}
+
+fn apply_textures(
+    pass: &mut RenderPass,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    textures_delta: egui::TexturesDelta,
+) {
+    for (id, img_del) in textures_delta.set {
+        pass.update_texture(device, queue, id, &img_del);
+    }
+    for id in textures_delta.free {
+        pass.free_texture(&id);
+    }
+}
+
+fn scale_size(size: [u32; 2], scale: f32) -> [u32; 2] {
+    [
+        (size[0] as f32 * scale).round() as u32,
+        (size[1] as f32 * scale).round() as u32,
+    ]
+}
+
+/// Same points at `scale` times the pixels.
+fn scale_descriptor(screen_descriptor: &ScreenDescriptor, scale: f32) -> ScreenDescriptor {
+    let [w, h] = scale_size(screen_descriptor.size_in_pixels, scale);
+    ScreenDescriptor {
+        size_in_pixels: [w.max(1), h.max(1)],
+        pixels_per_point: screen_descriptor.pixels_per_point * scale,
+    }
+}
+
+/// Install egui's request-repaint callback, so `egui::Context::request_repaint` from any thread
+/// wakes FLTK's event loop. The window is redrawn on the main thread, only in `RenderMode::Reactive`
+/// as the other modes repaint on their own cadence, `queue` is the window's `EguiState::event_queue`.
+///
+/// Keep the returned guard alive as long as the window, dropping it uninstalls the callback.
+#[must_use = "dropping the guard stops the repaints on request"]
+pub fn repaint_on_request<W>(ctx: &egui::Context, win: &W, queue: &EventQueue) -> RepaintOnRequest
+where
+    W: WindowExt + Clone + 'static,
+{
+    let requested = Arc::new(AtomicBool::new(false));
+    ctx.set_request_repaint_callback({
+        let requested = requested.clone();
+        move || {
+            requested.store(true, Ordering::Release);
+            app::awake();
+        }
+    });
+    let mut win = win.clone();
+    let queue = queue.clone();
+    // Runs on the main thread once app::wait() is woken up.
+    let check = app::add_check(move |_| {
+        if requested.swap(false, Ordering::Acquire) && queue.redraw_on_input() {
+            win.redraw();
+        }
+    });
+    RepaintOnRequest {
+        ctx: ctx.clone(),
+        check,
+    }
+}
+
+/// Guard returned by `repaint_on_request`.
+pub struct RepaintOnRequest {
+    ctx: egui::Context,
+    check: app::CheckHandle,
+}
+
+impl Drop for RepaintOnRequest {
+    fn drop(&mut self) {
+        self.ctx.set_request_repaint_callback(|| {});
+        if app::has_check(self.check) {
+            app::remove_check(self.check);
+        }
+    }
 }
 
 /// Frame time for CPU usage.
@@ -200,28 +562,244 @@ impl Default for FusedCursor {
     }
 }
 
+/// What Ctrl+mouse wheel does, see `EguiState::ctrl_wheel`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CtrlWheel {
+    /// Forward as `egui::Event::Zoom`, used by zoomable widgets like plots.
+    Zoom,
+    /// Scale the whole UI through `EguiState::set_relative_visual_scale`, like browsers do.
+    VisualScale,
+    /// Pass through as plain scroll.
+    Scroll,
+    /// Do nothing.
+    Ignore,
+}
+
+impl Default for CtrlWheel {
+    fn default() -> Self {
+        Self::Zoom
+    }
+}
+
+/// How often the window gets repainted, see `EguiState::schedule_repaint`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RenderMode {
+    /// Repaint on input or when egui asks for it (default).
+    Reactive,
+    /// Repaint whenever the event loop is idle, paced by the present mode (vsync).
+    Continuous,
+    /// Repaint continuously at the given frames per second.
+    FpsCapped(f32),
+}
+
+impl Default for RenderMode {
+    fn default() -> Self {
+        Self::Reactive
+    }
+}
+
 /// Shuttles FLTK's input and events to Egui
 pub struct EguiState {
     _window_resized: bool,
+    _window_visible: bool,
+    _app_modal: bool,
     pub fuse_cursor: FusedCursor,
     pub pointer_pos: egui::Pos2,
     input: egui::RawInput,
+    render_scale: f32,
     pub clipboard: Clipboard,
+    widgets: WidgetList,
+    render_mode: RenderMode,
+    clock: Box<dyn Clock>,
+    frame_timer: FrameTimer,
+    geometry: Option<WindowGeometry>,
+    queue: EventQueue,
+    native_ppu: f32,
+    screen_num: i32,
     /// default value is 12.0
     pub scroll_factor: f32,
     /// default value is 8.0
     pub zoom_factor: f32,
+    /// What Ctrl+wheel does, default is `CtrlWheel::Zoom`.
+    pub ctrl_wheel: CtrlWheel,
+    /// Visual scale range for `CtrlWheel::VisualScale`, relative to the screen's scale.
+    /// default value is (0.5, 3.0)
+    pub visual_scale_range: (f32, f32),
     _mouse_btn_pressed: bool,
+    /// Part of the window this state covers, in FLTK units.
+    region: Option<(i32, i32, i32, i32)>,
+    pointer_in_region: bool,
+    /// Physical size of the whole window.
+    pixel_size: (i32, i32),
     pub screen_descriptor: ScreenDescriptor,
 }
 
 impl EguiState {
+    /// Input state of `window`, e.g: for an extra region (see `set_region`) next to the one
+    /// returned by `begin_with`.
+    pub fn new<W>(window: &mut W) -> Self
+    where
+        W: WindowExt + PPU,
+    {
+        let ppu = window.pixels_per_unit();
+        // Physical size, FLTK's width()/height() are logical units on HiDPI screens.
+        let x = window.pixel_w();
+        let y = window.pixel_h();
+        let rect = egui::vec2(x as _, y as _) / ppu;
+        let screen_rect = egui::Rect::from_min_size(egui::Pos2::new(0f32, 0f32), rect);
+
+        Self {
+            _window_resized: false,
+            _window_visible: x > 0 && y > 0,
+            _app_modal: false,
+            fuse_cursor: FusedCursor::new(),
+            pointer_pos: egui::Pos2::new(0.0, 0.0),
+            input: egui::RawInput {
+                screen_rect: Some(screen_rect),
+                pixels_per_point: Some(ppu),
+                ..Default::default()
+            },
+            render_scale: 1.0,
+            clipboard: clipboard::Clipboard::default(),
+            widgets: WidgetList::default(),
+            render_mode: RenderMode::Reactive,
+            clock: Box::new(RealClock::default()),
+            frame_timer: FrameTimer::default(),
+            geometry: None,
+            queue: EventQueue::new(),
+            native_ppu: ppu,
+            region: None,
+            pointer_in_region: false,
+            pixel_size: (x, y),
+            screen_num: window.screen_num(),
+            _mouse_btn_pressed: false,
+            scroll_factor: 12.0,
+            zoom_factor: 8.0,
+            ctrl_wheel: CtrlWheel::Zoom,
+            visual_scale_range: (0.5, 3.0),
+            screen_descriptor: ScreenDescriptor {
+                size_in_pixels: [x as _, y as _],
+                pixels_per_point: ppu,
+            },
+        }
+    }
+
     /// Conveniece method bundling the necessary components for input/event handling
     pub fn fuse_input<W>(&mut self, win: &mut W, event: enums::Event)
     where
         W: WindowExt + PPU,
     {
+        let pending = self.input.events.len();
+        let resized = self._window_resized;
         input_to_egui(win, event, self);
+        if self.render_mode == RenderMode::Reactive
+            && (self.input.events.len() != pending || (!resized && self._window_resized))
+        {
+            // New input arrived, repaint now instead of waiting for the scheduled wakeup.
+            self.cancel_repaint();
+            win.redraw();
+        }
+    }
+
+    /// Shared input queue for the window's handle callback, unlike `fuse_input` it doesn't need
+    /// the state to be borrowed, so events arriving while the draw callback runs aren't dropped.
+    pub fn event_queue(&self) -> EventQueue {
+        self.queue.clone()
+    }
+
+    /// Check if input is waiting to be taken by the next frame.
+    pub fn has_input(&self) -> bool {
+        !self.input.events.is_empty() || !self.queue.is_empty() || self._window_resized
+    }
+
+    /// Fuse the events waiting in the `event_queue`.
+    pub fn fuse_queue(&mut self) {
+        if self.queue.is_empty() {
+            return;
+        }
+        for ev in self.queue.drain() {
+            translate_event(self, ev);
+        }
+    }
+
+    /// Schedule the next redraw of the window according to the `RenderMode` without blocking,
+    /// replacing the previously scheduled one. Call it at the end of the draw callback.
+    ///
+    /// `repaint_after` (from `FullOutput`) is honored in `RenderMode::Reactive` only.
+    pub fn schedule_repaint<W>(&mut self, win: &W, repaint_after: Duration)
+    where
+        W: WindowExt + Clone + 'static,
+    {
+        self.cancel_repaint();
+        match self.render_mode {
+            RenderMode::Reactive => {
+                self.stop_continuous_repaint();
+                let mut repaint_after = self.clock.repaint_delay(repaint_after);
+                if !self.queue.is_empty() {
+                    // Events queued while drawing, e.g: a resize by the UI, their redraw was lost.
+                    repaint_after = Duration::ZERO;
+                }
+                if repaint_after != Duration::MAX {
+                    // Even a zero delay goes through a timeout, FLTK clears the damage of a
+                    // redraw() requested while drawing.
+                    self.add_repaint_timeout(win, repaint_after);
+                }
+            }
+            RenderMode::Continuous => {
+                let mut repaint = self.queue.repaint();
+                if repaint.idle.is_none() {
+                    let mut win = win.clone();
+                    repaint.idle = Some(app::add_idle3(move |_| {
+                        win.redraw();
+                    }));
+                }
+            }
+            RenderMode::FpsCapped(fps) => {
+                let mut repaint = self.queue.repaint();
+                if repaint.ticker.is_none() {
+                    // Re-armed from the callback, so the period doesn't include the frame time.
+                    let period = 1.0 / fps.max(1.0) as f64;
+                    let mut win = win.clone();
+                    repaint.ticker = Some(app::add_timeout3(period, move |handle| {
+                        win.redraw();
+                        app::repeat_timeout3(period, handle);
+                    }));
+                }
+            }
+        }
+    }
+
+    fn add_repaint_timeout<W>(&mut self, win: &W, delay: Duration)
+    where
+        W: WindowExt + Clone + 'static,
+    {
+        let mut win = win.clone();
+        self.queue.repaint().timeout = Some(app::add_timeout3(delay.as_secs_f64(), move |_| {
+            win.redraw();
+        }));
+    }
+
+    /// Cancel the scheduled repaint wakeup, if any.
+    pub fn cancel_repaint(&mut self) {
+        self.queue.repaint().cancel_timeout();
+    }
+
+    fn stop_continuous_repaint(&mut self) {
+        self.queue.repaint().stop_continuous();
+    }
+
+    /// Switch the `RenderMode`, takes effect with the next `schedule_repaint`.
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        if self.render_mode != mode {
+            self.render_mode = mode;
+            self.queue.set_redraw_on_input(mode == RenderMode::Reactive);
+            self.cancel_repaint();
+            self.stop_continuous_repaint();
+        }
+    }
+
+    pub fn render_mode(&self) -> RenderMode {
+        self.render_mode
     }
 
     pub fn window_resized(&mut self) -> bool {
@@ -230,24 +808,94 @@ impl EguiState {
         tmp
     }
 
+    /// Last known geometry of the window, recorded on resize/move and hide.
+    pub fn window_geometry(&self) -> Option<WindowGeometry> {
+        self.geometry
+    }
+
+    fn record_geometry(&mut self, captured: Option<WindowGeometry>) {
+        if let Some(captured) = captured {
+            match &mut self.geometry {
+                Some(geometry) => geometry.merge(captured),
+                None => self.geometry = Some(captured),
+            }
+        }
+    }
+
+    /// Check if the window is currently shown (not minimized, hidden or zero sized).
+    pub fn window_visible(&self) -> bool {
+        self._window_visible
+    }
+
+    /// Make the window FLTK's grab while an egui modal is shown, so other FLTK windows of the app
+    /// stop accepting input, and restore the previous grab once `modal` is false again.
+    /// Hiding the window releases it as well.
+    pub fn set_app_modal<W>(&mut self, win: &W, modal: bool)
+    where
+        W: WindowExt + Clone,
+    {
+        if self._app_modal == modal {
+            return;
+        }
+        self._app_modal = modal;
+        if modal {
+            set_modal_window(win);
+        } else {
+            release_modal_window(win);
+        }
+    }
+
+    /// Check if the window is currently application-modal.
+    pub fn app_modal(&self) -> bool {
+        self._app_modal
+    }
+
     pub fn mouse_btn_pressed(&self) -> bool {
         self._mouse_btn_pressed
     }
 
+    /// Check if the window (or embedded pane) has the keyboard focus, with a region set:
+    /// whether it was the region clicked last.
+    pub fn has_focus(&self) -> bool {
+        self.input.has_focus
+    }
+
     /// Convenience method for outputting what egui emits each frame
     pub fn fuse_output<W>(&mut self, win: &mut W, egui_output: egui::PlatformOutput)
     where
         W: WindowExt,
     {
+        self.queue.set_drawing(false);
         if win.damage() {
             win.clear_damage();
         }
 
+        self.widgets.collect(&egui_output.events, self.pointer_pos);
+
         let copied_text = &egui_output.copied_text;
         if !copied_text.is_empty() {
             self.clipboard.set(copied_text.into());
         }
-        translate_cursor(win, &mut self.fuse_cursor, egui_output.cursor_icon);
+        // The region under the pointer owns the window's cursor.
+        if self.region.is_none() || self.pointer_in_region || self._mouse_btn_pressed {
+            translate_cursor(win, &mut self.fuse_cursor, egui_output.cursor_icon);
+        }
+    }
+
+    /// Widgets reported by egui's output events during the last frame passed to `fuse_output`.
+    pub fn widgets(&self) -> &WidgetList {
+        &self.widgets
+    }
+
+    /// Set what to drag out of the window while an egui drag is in progress, e.g: when `Response::dragged()`.
+    /// The native drag starts once the pointer crosses the window edge, the payload is cleared when the button is released.
+    /// The window's events must go through `EventQueue::push`, which starts the drag.
+    pub fn set_drag_payload(&mut self, payload: Option<DragPayload>) {
+        self.queue.set_drag_payload(payload);
+    }
+
+    pub fn drag_payload(&self) -> Option<DragPayload> {
+        self.queue.drag_payload()
     }
 
     /// Set visual scale, e.g: 0.8, 1.5, 2.0 .etc (default is 1.0)
@@ -267,17 +915,151 @@ impl EguiState {
         self.screen_descriptor.pixels_per_point
     }
 
+    /// Visual scale relative to the screen's scale, 1.0 unless changed.
+    pub fn visual_scale(&self) -> f32 {
+        self.pixels_per_point() / self.native_ppu
+    }
+
+    /// Set the visual scale relative to the screen's scale, clamped to `visual_scale_range`.
+    pub fn set_relative_visual_scale(&mut self, scale: f32) {
+        let (min, max) = self.visual_scale_range;
+        let scale = scale.max(min).min(max);
+        self.set_visual_scale(scale * self.native_ppu);
+        self._window_resized = true;
+    }
+
+    /// Pixels per FLTK unit of the screen the window is on.
+    pub fn pixels_per_unit(&self) -> f32 {
+        self.native_ppu
+    }
+
+    /// Restrict this state to a part of the window (FLTK units), so several contexts can share
+    /// one surface: pointer events are routed by position and keys go to the region clicked last.
+    /// Update it when the window is resized, `None` covers the whole window (default).
+    pub fn set_region(&mut self, region: Option<(i32, i32, i32, i32)>) {
+        if region == self.region {
+            return;
+        }
+        self.region = region;
+        if region.is_none() {
+            self.pointer_in_region = false;
+        }
+        self.set_pixel_size(self.pixel_size);
+        self.set_visual_scale(self.pixels_per_point());
+        self._window_resized = true;
+    }
+
+    pub fn region(&self) -> Option<(i32, i32, i32, i32)> {
+        self.region
+    }
+
+    /// Top-left corner of the region in physical pixels.
+    pub fn region_origin(&self) -> [u32; 2] {
+        match self.region {
+            Some((x, y, _, _)) => [self.to_pixels(x) as u32, self.to_pixels(y) as u32],
+            None => [0, 0],
+        }
+    }
+
+    /// Bundle a frame's output for `Painter::paint_regions`, `pass` is the region's render pass.
+    pub fn region_paint(
+        &self,
+        pass: usize,
+        clipped_primitives: Vec<egui::ClippedPrimitive>,
+        textures_delta: egui::TexturesDelta,
+    ) -> RegionPaint {
+        RegionPaint {
+            pass,
+            origin: self.region_origin(),
+            screen_descriptor: ScreenDescriptor {
+                size_in_pixels: self.screen_descriptor.size_in_pixels,
+                pixels_per_point: self.screen_descriptor.pixels_per_point,
+            },
+            clipped_primitives,
+            textures_delta,
+        }
+    }
+
+    /// Size egui renders at, the region's if one is set.
+    fn set_pixel_size(&mut self, pixel_size: (i32, i32)) {
+        self.pixel_size = pixel_size;
+        let (w, h) = match self.region {
+            Some((_, _, w, h)) => (self.to_pixels(w), self.to_pixels(h)),
+            None => pixel_size,
+        };
+        self.screen_descriptor.size_in_pixels = [w.max(0) as _, h.max(0) as _];
+    }
+
+    fn to_pixels(&self, units: i32) -> i32 {
+        (units as f32 * self.native_ppu).round().max(0.0) as i32
+    }
+
+    /// Re-query the scale when the window moved to another screen or FLTK's screen scale
+    /// changed (e.g: Ctrl+Plus/Minus/0), keeping the visual scale relative to the screen's one.
+    fn update_native_scale(&mut self, screen_num: i32, ppu: f32) {
+        if screen_num == self.screen_num && ppu == self.native_ppu {
+            return;
+        }
+        self.screen_num = screen_num;
+        if ppu > 0.0 && ppu != self.native_ppu {
+            let ppp = self.pixels_per_point() * ppu / self.native_ppu;
+            self.native_ppu = ppu;
+            self.screen_descriptor.pixels_per_point = ppp;
+        }
+    }
+
+    /// Take the input gathered since the last frame, also reads the `Clock` for `RawInput::time`
+    /// and feeds the smoothed frame interval into `RawInput::predicted_dt`.
     pub fn take_input(&mut self) -> egui::RawInput {
+        self.queue.set_drawing(true);
+        self.fuse_queue();
+        let now = self.clock.now();
+        if self.input.time.is_none() {
+            self.input.time = Some(now);
+        }
+        self.input.predicted_dt = self.frame_timer.begin_frame(now);
         let pixels_per_point = self.input.pixels_per_point;
-        let take = self.input.take();
+        let mut take = self.input.take();
         self.input.pixels_per_point = Some(self.screen_descriptor.pixels_per_point);
         if let Some(ppp) = pixels_per_point {
             self.screen_descriptor.pixels_per_point = ppp;
         }
+        // Points don't change, only the resolution egui tessellates and rasterizes fonts at.
+        take.pixels_per_point = Some(self.screen_descriptor.pixels_per_point * self.render_scale);
         take
     }
 
-    /// Set start time for egui timer related activity.
+    /// Scale egui tessellates at, set by `Painter::apply_render_scale` so shapes and fonts are
+    /// built for the offscreen resolution instead of being stretched.
+    pub fn render_scale(&self) -> f32 {
+        self.render_scale
+    }
+
+    /// Smoothed frame interval in seconds, as fed to egui.
+    pub fn predicted_dt(&self) -> f32 {
+        self.frame_timer.predicted_dt()
+    }
+
+    /// Last, average and max frame interval over the recent history.
+    pub fn frame_stats(&self) -> FrameStats {
+        self.frame_timer.stats()
+    }
+
+    /// Recent frame intervals in seconds, oldest first.
+    pub fn frame_times(&self) -> impl Iterator<Item = f32> + '_ {
+        self.frame_timer.history()
+    }
+
+    /// Replace the time source, e.g: `FixedStepClock` or `ManualClock` for reproducible frames.
+    pub fn set_clock<C>(&mut self, clock: C)
+    where
+        C: Clock + 'static,
+    {
+        self.clock = Box::new(clock);
+        self.frame_timer = FrameTimer::default();
+    }
+
+    /// Set start time for egui timer related activity, overrides the `Clock` for the next frame.
     pub fn start_time(&mut self, elapsed: f64) {
         self.input.time = Some(elapsed);
     }
@@ -288,21 +1070,123 @@ pub fn input_to_egui<W>(win: &mut W, event: enums::Event, state: &mut EguiState)
 where
     W: WindowExt + PPU,
 {
-    match event {
+    if !input_queue::is_egui_event(event) {
+        return;
+    }
+    // Keep the order of events queued while the state was borrowed.
+    state.fuse_queue();
+    if event == enums::Event::Hide {
+        release_modal_window(win);
+    }
+    translate_event(state, FltkEvent::capture(win, event));
+}
+
+fn translate_modifiers(keymod: enums::EventState) -> egui::Modifiers {
+    egui::Modifiers {
+        alt: (keymod & enums::EventState::Alt == enums::EventState::Alt),
+        ctrl: (keymod & enums::EventState::Ctrl == enums::EventState::Ctrl),
+        shift: (keymod & enums::EventState::Shift == enums::EventState::Shift),
+        mac_cmd: keymod & enums::EventState::Meta == enums::EventState::Meta,
+
+        //TOD: Test on both windows and mac
+        command: (keymod & enums::EventState::Command == enums::EventState::Command),
+    }
+}
+
+fn translate_mouse_button(button: app::MouseButton) -> Option<egui::PointerButton> {
+    match button {
+        app::MouseButton::Left => Some(egui::PointerButton::Primary),
+        app::MouseButton::Middle => Some(egui::PointerButton::Middle),
+        app::MouseButton::Right => Some(egui::PointerButton::Secondary),
+        _ => None,
+    }
+}
+
+/// Translates a captured FLTK event, doesn't touch the window so queued events can be fused later.
+fn translate_event(state: &mut EguiState, ev: FltkEvent) {
+    if let Some((rx, ry, rw, rh)) = state.region {
+        let (x, y) = ev.coords;
+        let inside = x >= rx && y >= ry && x < rx + rw && y < ry + rh;
+        let pressed = state._mouse_btn_pressed;
+        match ev.event {
+            // Keys go to the region clicked last.
+            enums::Event::Push => {
+                state.input.has_focus = inside;
+                if !inside {
+                    return;
+                }
+            }
+            enums::Event::Released if !pressed => return,
+            enums::Event::Move | enums::Event::Drag => {
+                if inside {
+                    state.pointer_in_region = true;
+                } else if !pressed {
+                    // Drags keep going to the region they started in.
+                    if std::mem::take(&mut state.pointer_in_region) {
+                        state.input.events.push(egui::Event::PointerGone);
+                    }
+                    return;
+                }
+            }
+            enums::Event::MouseWheel if !inside => return,
+            enums::Event::KeyDown | enums::Event::KeyUp if !state.input.has_focus => return,
+            enums::Event::Focus => return,
+            enums::Event::Unfocus => {
+                state.input.modifiers = Default::default();
+                return;
+            }
+            enums::Event::Leave => state.pointer_in_region = false,
+            _ => (),
+        }
+    }
+    match ev.event {
         enums::Event::Resize => {
-            state.screen_descriptor.size_in_pixels = [win.width() as _, win.height() as _];
+            let (w, h) = ev.pixel_size;
+            state._window_visible = w > 0 && h > 0;
+            if state._window_visible {
+                state.record_geometry(ev.geometry);
+                state.update_native_scale(ev.screen_num, ev.ppu);
+                state.set_pixel_size((w, h));
+                state.set_visual_scale(state.pixels_per_point());
+                state._window_resized = true;
+            } else {
+                // Zero sized window (e.g. minimized on some platforms), stop painting.
+                state.screen_descriptor.size_in_pixels = [0, 0];
+            }
+        }
+        enums::Event::Hide => {
+            state.record_geometry(ev.geometry);
+            // The modal was released when the event arrived, a hidden window must not keep the
+            // rest of the app locked.
+            state._app_modal = false;
+            state._window_visible = false;
+            state.screen_descriptor.size_in_pixels = [0, 0];
+            state.cancel_repaint();
+            state.stop_continuous_repaint();
+        }
+        enums::Event::Show => {
+            let (w, h) = ev.pixel_size;
+            state.update_native_scale(ev.screen_num, ev.ppu);
+            state._window_visible = true;
+            state.set_pixel_size((w, h));
             state.set_visual_scale(state.pixels_per_point());
+            // Force a repaint after being restored.
             state._window_resized = true;
         }
+        enums::Event::Focus => state.input.has_focus = true,
+        enums::Event::Unfocus => {
+            state.input.has_focus = false;
+            // Key releases go to the focused widget now, don't keep modifiers stuck.
+            state.input.modifiers = Default::default();
+        }
+        enums::Event::Leave => {
+            if !state._mouse_btn_pressed {
+                state.input.events.push(egui::Event::PointerGone);
+            }
+        }
         //MouseButonLeft pressed is the only one needed by egui
         enums::Event::Push => {
-            let mouse_btn = match app::event_mouse_button() {
-                app::MouseButton::Left => Some(egui::PointerButton::Primary),
-                app::MouseButton::Middle => Some(egui::PointerButton::Middle),
-                app::MouseButton::Right => Some(egui::PointerButton::Secondary),
-                _ => None,
-            };
-            if let Some(pressed) = mouse_btn {
+            if let Some(pressed) = translate_mouse_button(ev.mouse_button) {
                 state._mouse_btn_pressed = true;
                 state.input.events.push(egui::Event::PointerButton {
                     pos: state.pointer_pos,
@@ -316,13 +1200,7 @@ where
         //MouseButonLeft pressed is the only one needed by egui
         enums::Event::Released => {
             // fix unreachable, we can use Option.
-            let mouse_btn = match app::event_mouse_button() {
-                app::MouseButton::Left => Some(egui::PointerButton::Primary),
-                app::MouseButton::Middle => Some(egui::PointerButton::Middle),
-                app::MouseButton::Right => Some(egui::PointerButton::Secondary),
-                _ => None,
-            };
-            if let Some(released) = mouse_btn {
+            if let Some(released) = translate_mouse_button(ev.mouse_button) {
                 state._mouse_btn_pressed = false;
                 state.input.events.push(egui::Event::PointerButton {
                     pos: state.pointer_pos,
@@ -334,9 +1212,26 @@ where
         }
 
         enums::Event::Move | enums::Event::Drag => {
-            let (x, y) = app::event_coords();
-            let ppp = state.pixels_per_point();
-            state.pointer_pos = pos2(x as f32 / ppp, y as f32 / ppp);
+            if ev.dnd_started {
+                // The button is released in the drop target, end the egui drag here.
+                state._mouse_btn_pressed = false;
+                state.input.events.push(egui::Event::PointerButton {
+                    pos: state.pointer_pos,
+                    button: egui::PointerButton::Primary,
+                    pressed: false,
+                    modifiers: state.input.modifiers,
+                });
+                state.input.events.push(egui::Event::PointerGone);
+                return;
+            }
+            let (x, y) = ev.coords;
+            // Region relative, FLTK units to pixels to points.
+            let (x, y) = match state.region {
+                Some((rx, ry, _, _)) => (x - rx, y - ry),
+                None => (x, y),
+            };
+            let scale = state.native_ppu / state.pixels_per_point();
+            state.pointer_pos = pos2(x as f32 * scale, y as f32 * scale);
             state
                 .input
                 .events
@@ -344,17 +1239,8 @@ where
         }
 
         enums::Event::KeyUp => {
-            if let Some(key) = translate_virtual_key_code(app::event_key()) {
-                let keymod = app::event_state();
-                state.input.modifiers = egui::Modifiers {
-                    alt: (keymod & enums::EventState::Alt == enums::EventState::Alt),
-                    ctrl: (keymod & enums::EventState::Ctrl == enums::EventState::Ctrl),
-                    shift: (keymod & enums::EventState::Shift == enums::EventState::Shift),
-                    mac_cmd: keymod & enums::EventState::Meta == enums::EventState::Meta,
-
-                    //TOD: Test on both windows and mac
-                    command: (keymod & enums::EventState::Command == enums::EventState::Command),
-                };
+            if let Some(key) = translate_virtual_key_code(ev.key) {
+                state.input.modifiers = translate_modifiers(ev.key_state);
                 state.input.events.push(egui::Event::Key {
                     key,
                     pressed: false,
@@ -364,25 +1250,11 @@ where
         }
 
         enums::Event::KeyDown => {
-            if let Some(c) = app::event_text().chars().next() {
-                if let Some(del) = app::compose() {
-                    state.input.events.push(egui::Event::Text(c.to_string()));
-                    if del != 0 {
-                        app::compose_reset();
-                    }
-                }
+            if let Some(text) = ev.text {
+                state.input.events.push(egui::Event::Text(text));
             }
-            if let Some(key) = translate_virtual_key_code(app::event_key()) {
-                let keymod = app::event_state();
-                state.input.modifiers = egui::Modifiers {
-                    alt: (keymod & enums::EventState::Alt == enums::EventState::Alt),
-                    ctrl: (keymod & enums::EventState::Ctrl == enums::EventState::Ctrl),
-                    shift: (keymod & enums::EventState::Shift == enums::EventState::Shift),
-                    mac_cmd: keymod & enums::EventState::Meta == enums::EventState::Meta,
-
-                    //TOD: Test on both windows and mac
-                    command: (keymod & enums::EventState::Command == enums::EventState::Command),
-                };
+            if let Some(key) = translate_virtual_key_code(ev.key) {
+                state.input.modifiers = translate_modifiers(ev.key_state);
                 state.input.events.push(egui::Event::Key {
                     key,
                     pressed: true,
@@ -403,46 +1275,49 @@ where
         }
 
         enums::Event::MouseWheel => {
-            if app::is_event_ctrl() {
-                let zoom_factor = state.zoom_factor;
-                match app::event_dy() {
-                    app::MouseWheel::Up => {
-                        let delta = vec2(1., -1.) * zoom_factor;
-
-                        // Treat as zoom in:
-                        state
-                            .input
-                            .events
-                            .push(Event::Zoom((delta.y / 200.0).exp()));
-                    }
-                    app::MouseWheel::Down => {
-                        let delta = vec2(-1., 1.) * zoom_factor;
-
-                        // Treat as zoom out:
-                        state
-                            .input
-                            .events
-                            .push(Event::Zoom((delta.y / 200.0).exp()));
+            let zoom = |zoom_factor: f32| match ev.wheel_dy {
+                // Treat as zoom in:
+                app::MouseWheel::Up => Some((vec2(1., -1.) * zoom_factor).y / 200.0),
+                // Treat as zoom out:
+                app::MouseWheel::Down => Some((vec2(-1., 1.) * zoom_factor).y / 200.0),
+                _ => None,
+            };
+            let policy = if ev.ctrl {
+                state.ctrl_wheel
+            } else {
+                CtrlWheel::Scroll
+            };
+            match policy {
+                CtrlWheel::Zoom => {
+                    if let Some(delta) = zoom(state.zoom_factor) {
+                        state.input.events.push(Event::Zoom(delta.exp()));
                     }
-                    _ => (),
                 }
-            } else {
-                let scroll_factor = state.scroll_factor;
-                match app::event_dy() {
-                    app::MouseWheel::Up => {
-                        state.input.events.push(Event::Scroll(Vec2 {
-                            x: 0.,
-                            y: -scroll_factor,
-                        }));
+                CtrlWheel::VisualScale => {
+                    if let Some(delta) = zoom(state.zoom_factor) {
+                        let scale = state.visual_scale() * delta.exp();
+                        state.set_relative_visual_scale(scale);
                     }
-                    app::MouseWheel::Down => {
-                        state.input.events.push(Event::Scroll(Vec2 {
-                            x: 0.,
-                            y: scroll_factor,
-                        }));
+                }
+                CtrlWheel::Scroll => {
+                    let scroll_factor = state.scroll_factor;
+                    match ev.wheel_dy {
+                        app::MouseWheel::Up => {
+                            state.input.events.push(Event::Scroll(Vec2 {
+                                x: 0.,
+                                y: -scroll_factor,
+                            }));
+                        }
+                        app::MouseWheel::Down => {
+                            state.input.events.push(Event::Scroll(Vec2 {
+                                x: 0.,
+                                y: scroll_factor,
+                            }));
+                        }
+                        _ => (),
                     }
-                    _ => (),
                 }
+                CtrlWheel::Ignore => (),
             }
         }
 
@@ -770,14 +1645,10 @@ pub trait RWHandleExt {
     fn use_compat(&self) -> RwhCompat;
 }
 
-impl RWHandleExt for fltk::window::Window {
-    fn use_compat(&self) -> RwhCompat {
-        RwhCompat(self.raw_handle())
-    }
-}
-
-#[cfg(feature = "enable-glwindow")]
-impl RWHandleExt for fltk::window::GlWindow {
+impl<W> RWHandleExt for W
+where
+    W: WindowExt,
+{
     fn use_compat(&self) -> RwhCompat {
         RwhCompat(self.raw_handle())
     }
@@ -0,0 +1,62 @@
+use fltk::{
+    app,
+    prelude::{WidgetBase, WindowExt},
+    window,
+};
+use std::cell::RefCell;
+
+/// Application-modal window and the grab it replaced.
+struct Modal {
+    win: usize,
+    previous_grab: Option<window::Window>,
+}
+
+thread_local! {
+    static MODAL: RefCell<Option<Modal>> = RefCell::new(None);
+}
+
+/// Make `win` FLTK's grab, so it receives the events of every window of the app.
+///
+/// FLTK's dialogs and menus save and restore the grab while they're open. On X11 the grab also
+/// holds the pointer, like a popup menu does.
+pub(crate) fn set_modal_window<W>(win: &W)
+where
+    W: WindowExt + Clone,
+{
+    MODAL.with(|modal| {
+        let mut modal = modal.borrow_mut();
+        let previous_grab = match modal.take() {
+            // Keep the grab of the app, not the one of the modal being replaced.
+            Some(modal) => modal.previous_grab,
+            None => app::grab()
+                .map(|grab| unsafe { window::Window::from_widget_ptr(grab.as_widget_ptr()) }),
+        };
+        *modal = Some(Modal {
+            win: win.as_widget_ptr() as usize,
+            previous_grab,
+        });
+    });
+    app::set_grab(Some(win.clone()));
+}
+
+/// Give the grab back if `win` is the modal window, e.g: when it gets hidden.
+pub(crate) fn release_modal_window<W>(win: &W)
+where
+    W: WindowExt,
+{
+    let ptr = win.as_widget_ptr() as usize;
+    let released = MODAL.with(|modal| {
+        let mut modal = modal.borrow_mut();
+        match modal.as_ref() {
+            Some(current) if current.win == ptr => modal.take(),
+            _ => None,
+        }
+    });
+    if let Some(released) = released {
+        // Someone else took the grab meanwhile, leave theirs alone.
+        let grabbed = app::grab().map(|grab| grab.as_widget_ptr() as usize);
+        if grabbed.is_none() || grabbed == Some(ptr) {
+            app::set_grab(released.previous_grab);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const MEMORY_KEY: &str = "egui_memory";
+
+/// Key-value storage persisted as a ron file, holds `egui::Memory` and app state.
+pub struct Storage {
+    path: PathBuf,
+    kv: HashMap<String, String>,
+    dirty: bool,
+    /// How often `autosave` writes to disk, default is 30 seconds.
+    pub autosave_interval: Duration,
+}
+
+impl Storage {
+    /// Storage at `<config dir>/<app_id>/app.ron`, `None` if there's no config directory.
+    pub fn from_app_id(app_id: &str) -> Option<Self> {
+        let dirs = directories_next::ProjectDirs::from("", "", app_id)?;
+        Some(Self::from_path(dirs.config_dir().join("app.ron")))
+    }
+
+    /// Storage at the given file, existing content is loaded if readable.
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let kv = match std::fs::read_to_string(&path) {
+            Ok(content) => match ron::from_str(&content) {
+                Ok(kv) => kv,
+                Err(err) => {
+                    eprintln!("Failed to parse {}: {}", path.display(), err);
+                    HashMap::new()
+                }
+            },
+            Err(_) => HashMap::new(),
+        };
+        Self {
+            path,
+            kv,
+            dirty: false,
+            autosave_interval: Duration::from_secs(30),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get_string(&self, key: &str) -> Option<String> {
+        self.kv.get(key).cloned()
+    }
+
+    pub fn set_string(&mut self, key: &str, value: String) {
+        if self.kv.get(key) != Some(&value) {
+            self.kv.insert(key.to_owned(), value);
+            self.dirty = true;
+        }
+    }
+
+    /// Deserialize a value previously stored with `set_value`.
+    pub fn get_value<T>(&self, key: &str) -> Option<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let value = self.kv.get(key)?;
+        match ron::from_str(value) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                eprintln!("Failed to restore {:?}: {}", key, err);
+                None
+            }
+        }
+    }
+
+    pub fn set_value<T>(&mut self, key: &str, value: &T)
+    where
+        T: serde::Serialize,
+    {
+        match ron::to_string(value) {
+            Ok(value) => self.set_string(key, value),
+            Err(err) => eprintln!("Failed to store {:?}: {}", key, err),
+        }
+    }
+
+    /// Restore window positions, collapsed headers, scroll offsets .etc into `ctx`.
+    pub fn load_memory(&self, ctx: &egui::Context) {
+        if let Some(memory) = self.get_value::<egui::Memory>(MEMORY_KEY) {
+            *ctx.memory() = memory;
+        }
+    }
+
+    pub fn save_memory(&mut self, ctx: &egui::Context) {
+        let memory = ctx.memory().clone();
+        self.set_value(MEMORY_KEY, &memory);
+    }
+
+    /// Write to disk if anything changed.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let content = ron::ser::to_string_pretty(&self.kv, Default::default())
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+        std::fs::write(&self.path, content)?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fresh file under the temp dir, removed on drop.
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir()
+                .join(format!("egui-fltk-frontend-{}", std::process::id()))
+                .join(name);
+            let _ = std::fs::remove_file(&path);
+            Self(path)
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn values_round_trip_through_the_file() {
+        let file = TempFile::new("values.ron");
+        let mut storage = Storage::from_path(&file.0);
+        storage.set_value("scale", &1.5f32);
+        storage.set_value("name", &String::from("egui"));
+        assert_eq!(storage.get_value::<f32>("scale"), Some(1.5));
+        storage.flush().unwrap();
+
+        let storage = Storage::from_path(&file.0);
+        assert_eq!(storage.get_value::<f32>("scale"), Some(1.5));
+        assert_eq!(storage.get_value::<String>("name").as_deref(), Some("egui"));
+        assert_eq!(storage.get_value::<f32>("missing"), None);
+        // Stored, but not as the requested type.
+        assert_eq!(storage.get_value::<bool>("name"), None);
+    }
+
+    #[test]
+    fn flush_writes_only_changes() {
+        let file = TempFile::new("flush.ron");
+        let mut storage = Storage::from_path(&file.0);
+        storage.flush().unwrap();
+        assert!(!file.0.exists());
+
+        storage.set_string("key", "value".into());
+        storage.flush().unwrap();
+        assert!(file.0.exists());
+
+        // Same value, nothing to write.
+        std::fs::remove_file(&file.0).unwrap();
+        storage.set_string("key", "value".into());
+        storage.flush().unwrap();
+        assert!(!file.0.exists());
+    }
+
+    #[test]
+    fn memory_round_trip() {
+        let file = TempFile::new("memory.ron");
+        let ctx = egui::Context::default();
+        let id = egui::Id::new("header");
+        ctx.memory().data.insert_persisted(id, 42u32);
+        let mut storage = Storage::from_path(&file.0);
+        storage.save_memory(&ctx);
+        storage.flush().unwrap();
+
+        let ctx = egui::Context::default();
+        Storage::from_path(&file.0).load_memory(&ctx);
+        assert_eq!(ctx.memory().data.get_persisted::<u32>(id), Some(42));
+    }
+
+    #[test]
+    fn corrupt_file_starts_empty() {
+        let file = TempFile::new("corrupt.ron");
+        std::fs::create_dir_all(file.0.parent().unwrap()).unwrap();
+        std::fs::write(&file.0, "{ not ron").unwrap();
+        let mut storage = Storage::from_path(&file.0);
+        assert_eq!(storage.get_string("key"), None);
+
+        // The next flush replaces the corrupt content.
+        storage.set_string("key", "value".into());
+        storage.flush().unwrap();
+        let storage = Storage::from_path(&file.0);
+        assert_eq!(storage.get_string("key").as_deref(), Some("value"));
+    }
+}
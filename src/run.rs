@@ -0,0 +1,109 @@
+#[cfg(feature = "persistence")]
+use crate::Storage;
+use crate::{disable_screen_scaling, EguiWindow, Frame, RunError, WgpuOptions};
+use fltk::{
+    app,
+    prelude::{WidgetExt, WindowExt},
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// Options for `run`.
+pub struct NativeOptions {
+    pub title: String,
+    /// Initial window size (width, height).
+    pub size: (i32, i32),
+    pub resizable: bool,
+    /// Follow FLTK's screen scaling (default), false forces scale 1.0, see `disable_screen_scaling`.
+    pub screen_scaling: bool,
+    pub wgpu: WgpuOptions,
+    /// Persist egui's memory and app state under the user's config directory for this id.
+    #[cfg(feature = "persistence")]
+    pub app_id: Option<String>,
+}
+
+impl Default for NativeOptions {
+    fn default() -> Self {
+        Self {
+            title: "egui".into(),
+            size: (800, 600),
+            resizable: true,
+            screen_scaling: true,
+            wgpu: WgpuOptions::default(),
+            #[cfg(feature = "persistence")]
+            app_id: None,
+        }
+    }
+}
+
+/// Application driven by `run`.
+pub trait App {
+    /// Called once before the first frame.
+    fn setup(&mut self, _ctx: &egui::Context, _frame: &mut Frame) {}
+
+    /// Called each time the UI needs repainting.
+    fn update(&mut self, ctx: &egui::Context, frame: &mut Frame);
+
+    /// Store app state, called on autosave and on exit.
+    #[cfg(feature = "persistence")]
+    fn save(&mut self, _storage: &mut Storage) {}
+
+    /// Called once after the window has been closed.
+    fn on_exit(&mut self) {}
+}
+
+/// Create the window and wgpu resources, then run `app` until the window is closed.
+pub fn run<A>(options: NativeOptions, app: A) -> Result<(), RunError>
+where
+    A: App + 'static,
+{
+    let fltk_app = app::App::default();
+
+    let mut window = EguiWindow::with_options(
+        0,
+        0,
+        options.size.0,
+        options.size.1,
+        &options.title,
+        options.wgpu,
+    );
+    window.set_pos(
+        (app::screen_size().0 as i32 - options.size.0) / 2,
+        (app::screen_size().1 as i32 - options.size.1) / 2,
+    );
+    window.make_resizable(options.resizable);
+    if !options.screen_scaling {
+        disable_screen_scaling(&*window);
+    }
+
+    let app = Rc::new(RefCell::new(app));
+    let mut needs_setup = true;
+    window.set_ui({
+        let app = app.clone();
+        move |ctx, frame| {
+            let mut app = app.borrow_mut();
+            if needs_setup {
+                needs_setup = false;
+                app.setup(ctx, frame);
+            }
+            app.update(ctx, frame);
+        }
+    });
+
+    #[cfg(feature = "persistence")]
+    if let Some(storage) = options.app_id.as_deref().and_then(Storage::from_app_id) {
+        window.set_storage(storage);
+        let app = app.clone();
+        window.set_on_save(move |storage| app.borrow_mut().save(storage));
+    }
+
+    window.show();
+
+    fltk_app.run()?;
+    #[cfg(feature = "persistence")]
+    window.save();
+    app.borrow_mut().on_exit();
+    match window.take_error() {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
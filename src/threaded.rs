@@ -0,0 +1,143 @@
+use fltk::{app, prelude::WindowExt};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Result of an egui frame computed on the worker thread, ready for `Painter::paint_jobs`.
+pub struct FrameOutput {
+    pub platform_output: egui::PlatformOutput,
+    pub clipped_primitives: Vec<egui::ClippedPrimitive>,
+    pub textures_delta: egui::TexturesDelta,
+    pub repaint_after: Duration,
+}
+
+/// Runs `egui::Context::run` and `tessellate` on a background thread, so heavy frames
+/// don't block FLTK's event handling.
+///
+/// Each draw: take the finished frame with `try_recv`, `submit(state)` then paint the frame.
+pub struct ThreadedUi {
+    input_tx: Option<mpsc::Sender<egui::RawInput>>,
+    output_rx: mpsc::Receiver<FrameOutput>,
+    worker: Option<thread::JoinHandle<()>>,
+    notified: Arc<AtomicBool>,
+    redraw_check: app::CheckHandle,
+    in_flight: bool,
+}
+
+impl ThreadedUi {
+    /// Spawn the worker, `win` is redrawn whenever a frame is ready.
+    pub fn spawn<W, F>(ctx: egui::Context, win: &W, mut ui: F) -> Self
+    where
+        W: WindowExt + Clone + 'static,
+        F: FnMut(&egui::Context) + Send + 'static,
+    {
+        let (input_tx, input_rx) = mpsc::channel::<egui::RawInput>();
+        let (output_tx, output_rx) = mpsc::channel();
+        let notified = Arc::new(AtomicBool::new(false));
+        let ready = Arc::new(AtomicBool::new(false));
+
+        let worker = {
+            let notified = notified.clone();
+            let ready = ready.clone();
+            thread::spawn(move || {
+                for input in input_rx {
+                    let output = ctx.run(input, &mut ui);
+                    let frame = FrameOutput {
+                        platform_output: output.platform_output,
+                        clipped_primitives: ctx.tessellate(output.shapes),
+                        textures_delta: output.textures_delta,
+                        repaint_after: output.repaint_after,
+                    };
+                    if output_tx.send(frame).is_err() {
+                        break;
+                    }
+                    notified.store(true, Ordering::Release);
+                    ready.store(true, Ordering::Release);
+                    app::awake();
+                }
+            })
+        };
+
+        // The window is redrawn on the main thread, once awake() wakes up the event loop.
+        let mut win = win.clone();
+        let redraw_check = app::add_check(move |_| {
+            if ready.swap(false, Ordering::Acquire) {
+                win.redraw();
+            }
+        });
+
+        Self {
+            input_tx: Some(input_tx),
+            output_rx,
+            worker: Some(worker),
+            notified,
+            redraw_check,
+            in_flight: false,
+        }
+    }
+
+    /// Check if the worker is busy with a frame.
+    pub fn in_flight(&self) -> bool {
+        self.in_flight
+    }
+
+    /// Hand the input to the worker, unless a frame is in flight or this draw was only
+    /// caused by the worker delivering a frame (input is kept by `EguiState` until then).
+    /// Call it after `try_recv`, which marks the delivered frame as no longer in flight.
+    ///
+    /// Returns whether the input was submitted.
+    pub fn submit(&mut self, state: &mut crate::EguiState) -> bool {
+        let from_worker = self.notified.swap(false, Ordering::Acquire);
+        if self.in_flight || (from_worker && !state.has_input()) {
+            return false;
+        }
+        if let Some(input_tx) = &self.input_tx {
+            if input_tx.send(state.take_input()).is_ok() {
+                self.in_flight = true;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Take the frames computed since the last call, merged into the latest one.
+    pub fn try_recv(&mut self) -> Option<FrameOutput> {
+        let mut latest: Option<FrameOutput> = None;
+        while let Ok(frame) = self.output_rx.try_recv() {
+            self.in_flight = false;
+            latest = Some(match latest {
+                Some(mut older) => {
+                    // Texture deltas and platform output of skipped frames can't be dropped.
+                    older.textures_delta.append(frame.textures_delta);
+                    older.platform_output.append(frame.platform_output);
+                    FrameOutput {
+                        platform_output: older.platform_output,
+                        clipped_primitives: frame.clipped_primitives,
+                        textures_delta: older.textures_delta,
+                        repaint_after: frame.repaint_after,
+                    }
+                }
+                None => frame,
+            });
+        }
+        latest
+    }
+}
+
+impl Drop for ThreadedUi {
+    fn drop(&mut self) {
+        if app::has_check(self.redraw_check) {
+            app::remove_check(self.redraw_check);
+        }
+        // Closing the input channel ends the worker loop.
+        self.input_tx.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
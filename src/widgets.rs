@@ -0,0 +1,280 @@
+use egui::{output::OutputEvent, Pos2, WidgetType};
+use std::fmt::Write;
+
+/// What kind of output event reported the widget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WidgetEventKind {
+    Clicked,
+    DoubleClicked,
+    TripleClicked,
+    FocusGained,
+    TextSelectionChanged,
+    ValueChanged,
+}
+
+impl WidgetEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Clicked => "clicked",
+            Self::DoubleClicked => "double_clicked",
+            Self::TripleClicked => "triple_clicked",
+            Self::FocusGained => "focus_gained",
+            Self::TextSelectionChanged => "text_selection_changed",
+            Self::ValueChanged => "value_changed",
+        }
+    }
+}
+
+/// Widget reported by egui during a frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WidgetEntry {
+    pub event: WidgetEventKind,
+    pub role: WidgetType,
+    pub label: Option<String>,
+    /// Text, numeric or selected value of the widget, if any.
+    pub value: Option<String>,
+    pub enabled: bool,
+    pub focused: bool,
+    /// Pointer position (in points) when the event fired, egui doesn't report widget rects.
+    pub pointer_pos: Option<Pos2>,
+}
+
+impl WidgetEntry {
+    fn from_event(event: &OutputEvent, pointer_pos: Pos2) -> Self {
+        let (kind, info) = match event {
+            OutputEvent::Clicked(info) => (WidgetEventKind::Clicked, info),
+            OutputEvent::DoubleClicked(info) => (WidgetEventKind::DoubleClicked, info),
+            OutputEvent::TripleClicked(info) => (WidgetEventKind::TripleClicked, info),
+            OutputEvent::FocusGained(info) => (WidgetEventKind::FocusGained, info),
+            OutputEvent::TextSelectionChanged(info) => {
+                (WidgetEventKind::TextSelectionChanged, info)
+            }
+            OutputEvent::ValueChanged(info) => (WidgetEventKind::ValueChanged, info),
+        };
+
+        let value = if let Some(text) = &info.current_text_value {
+            Some(text.clone())
+        } else if let Some(value) = info.value {
+            Some(value.to_string())
+        } else {
+            info.selected.map(|selected| selected.to_string())
+        };
+
+        let pointer_pos = match kind {
+            WidgetEventKind::Clicked
+            | WidgetEventKind::DoubleClicked
+            | WidgetEventKind::TripleClicked => Some(pointer_pos),
+            _ => None,
+        };
+
+        Self {
+            event: kind,
+            role: info.typ,
+            label: info.label.clone(),
+            value,
+            enabled: info.enabled,
+            focused: kind == WidgetEventKind::FocusGained
+                || kind == WidgetEventKind::TextSelectionChanged,
+            pointer_pos,
+        }
+    }
+}
+
+/// Per-frame list of widgets collected from egui's `PlatformOutput::events`.
+///
+/// Only widgets that emitted an output event during the frame (clicked, focused, changed .etc) are
+/// listed, not everything on screen, and egui doesn't report widget rects: the position is the
+/// pointer's at the time of a click.
+#[derive(Clone, Debug, Default)]
+pub struct WidgetList {
+    entries: Vec<WidgetEntry>,
+}
+
+impl WidgetList {
+    pub(crate) fn collect(&mut self, events: &[OutputEvent], pointer_pos: Pos2) {
+        self.entries.clear();
+        self.entries.extend(
+            events
+                .iter()
+                .map(|event| WidgetEntry::from_event(event, pointer_pos)),
+        );
+    }
+
+    pub fn entries(&self) -> &[WidgetEntry] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The widget which gained focus during the frame, if any.
+    pub fn focused(&self) -> Option<&WidgetEntry> {
+        self.entries.iter().rev().find(|entry| entry.focused)
+    }
+
+    /// Find the first widget with the given label.
+    pub fn find_by_label(&self, label: &str) -> Option<&WidgetEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.label.as_deref() == Some(label))
+    }
+
+    /// Dump the list as plain text, one widget per line.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for entry in &self.entries {
+            let _ = write!(out, "{:?} [{}]", entry.role, entry.event.as_str());
+            if let Some(label) = &entry.label {
+                let _ = write!(out, " label={:?}", label);
+            }
+            if let Some(value) = &entry.value {
+                let _ = write!(out, " value={:?}", value);
+            }
+            if !entry.enabled {
+                out.push_str(" disabled");
+            }
+            if entry.focused {
+                out.push_str(" focused");
+            }
+            if let Some(pos) = entry.pointer_pos {
+                let _ = write!(out, " at=({}, {})", pos.x, pos.y);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Dump the list as a JSON array.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("[");
+        for (i, entry) in self.entries.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write!(
+                out,
+                "{{\"role\":\"{:?}\",\"event\":\"{}\",\"label\":{},\"value\":{},\"enabled\":{},\"focused\":{},\"pointer_pos\":",
+                entry.role,
+                entry.event.as_str(),
+                json_opt_str(entry.label.as_deref()),
+                json_opt_str(entry.value.as_deref()),
+                entry.enabled,
+                entry.focused,
+            );
+            match entry.pointer_pos {
+                Some(pos) => {
+                    let _ = write!(out, "[{},{}]", pos.x, pos.y);
+                }
+                None => out.push_str("null"),
+            }
+            out.push('}');
+        }
+        out.push(']');
+        out
+    }
+}
+
+fn json_opt_str(value: Option<&str>) -> String {
+    let value = match value {
+        Some(value) => value,
+        None => return "null".into(),
+    };
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::WidgetInfo;
+
+    fn list(events: &[OutputEvent]) -> WidgetList {
+        let mut list = WidgetList::default();
+        list.collect(events, Pos2::new(10.0, 20.0));
+        list
+    }
+
+    #[test]
+    fn collects_entries() {
+        let list = list(&[
+            OutputEvent::Clicked(WidgetInfo::labeled(WidgetType::Button, "Ok")),
+            OutputEvent::FocusGained(WidgetInfo::text_edit("", "hello")),
+            OutputEvent::ValueChanged(WidgetInfo::slider(0.5, "Volume")),
+        ]);
+        assert_eq!(list.entries().len(), 3);
+
+        let ok = list.find_by_label("Ok").unwrap();
+        assert_eq!(ok.event, WidgetEventKind::Clicked);
+        assert_eq!(ok.pointer_pos, Some(Pos2::new(10.0, 20.0)));
+        assert!(!ok.focused);
+
+        let focused = list.focused().unwrap();
+        assert_eq!(focused.role, WidgetType::TextEdit);
+        assert_eq!(focused.value.as_deref(), Some("hello"));
+        assert_eq!(focused.pointer_pos, None);
+
+        let volume = list.find_by_label("Volume").unwrap();
+        assert_eq!(volume.value.as_deref(), Some("0.5"));
+    }
+
+    #[test]
+    fn collect_replaces_previous_frame() {
+        let mut list = list(&[OutputEvent::Clicked(WidgetInfo::labeled(
+            WidgetType::Button,
+            "Ok",
+        ))]);
+        list.collect(&[], Pos2::ZERO);
+        assert!(list.is_empty());
+        assert_eq!(list.to_json(), "[]");
+    }
+
+    #[test]
+    fn to_text() {
+        let list = list(&[OutputEvent::Clicked(WidgetInfo::labeled(
+            WidgetType::Button,
+            "Ok",
+        ))]);
+        assert_eq!(
+            list.to_text(),
+            "Button [clicked] label=\"Ok\" at=(10, 20)\n"
+        );
+    }
+
+    #[test]
+    fn to_json() {
+        let list = list(&[
+            OutputEvent::Clicked(WidgetInfo::labeled(WidgetType::Button, "Ok")),
+            OutputEvent::FocusGained(WidgetInfo::new(WidgetType::TextEdit)),
+        ]);
+        assert_eq!(
+            list.to_json(),
+            "[{\"role\":\"Button\",\"event\":\"clicked\",\"label\":\"Ok\",\"value\":null,\
+             \"enabled\":true,\"focused\":false,\"pointer_pos\":[10,20]},\
+             {\"role\":\"TextEdit\",\"event\":\"focus_gained\",\"label\":null,\"value\":null,\
+             \"enabled\":true,\"focused\":true,\"pointer_pos\":null}]"
+        );
+    }
+
+    #[test]
+    fn json_escapes_strings() {
+        assert_eq!(json_opt_str(None), "null");
+        assert_eq!(json_opt_str(Some("a\"b\\c")), "\"a\\\"b\\\\c\"");
+        assert_eq!(json_opt_str(Some("1\n2\t\u{1}")), "\"1\\n2\\t\\u0001\"");
+    }
+}